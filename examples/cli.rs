@@ -1,10 +1,11 @@
 // CLI - Cat Lister & Instantiator
 
 use mimicry::*;
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 #[allow(unused)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BOOL {
     a: bool,
 }
@@ -14,7 +15,7 @@ impl From<&str> for BOOL {
         let s = s.trim();
 
         // Adequate for this example
-        if s.to_ascii_lowercase() == "true" {
+        if s.eq_ignore_ascii_case("true") {
             BOOL { a: true }
         } else {
             BOOL { a: false }
@@ -25,133 +26,125 @@ impl From<&str> for BOOL {
 impl FromStr for BOOL {
     type Err = &'static str;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let a;
-        match s.parse::<bool>() {
-            Ok(value) => a = value,
+        let a = match s.parse::<bool>() {
+            Ok(value) => value,
             Err(_) => return Err("BOOL failed to parse as a boolean in from_str"),
         };
         Ok(BOOL { a })
     }
 }
 
+impl fmt::Display for BOOL {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.a)
+    }
+}
+
 #[allow(unused)]
 #[derive(Debug, Mimic)]
 enum CatSelector {
     None,
     Unicolor {
+        #[mimic(min_len = 1, max_len = 32, prompt = "Enter the cats name:")]
         name: String,
+        #[mimic(prompt = "Enter the cats color (get creative):")]
         color: String,
     },
     Mixed {
+        #[mimic(min_len = 1, max_len = 32, prompt = "Enter the cats name:")]
         name: String,
         // There is no implementation for From<&str> for bool so we have to make one ourselves
+        #[mimic(prompt = "Does this cat have stripes? (true/false)")]
         with_stripes: BOOL,
     },
     Tabby {
+        #[mimic(range = 0.0..=1.0, prompt = "How fluffy is this cat? (A decimal from 0 to 1):")]
         fluff_ratio: f32,
+        // Tabby coats come in only these patterns
+        #[mimic(one_of = ["classic", "mackerel", "spotted", "ticked"])]
+        #[mimic(prompt = "Which tabby pattern? (classic, mackerel, spotted, or ticked):")]
+        pattern: String,
     },
     Chungus {
+        #[mimic(prompt = "Chungus approximation (A positive integer):")]
         chung_ratio: usize,
     },
 }
 
-use std::io::{self, Write};
-
-pub fn get_input(prompt: &str) -> String {
-    let mut input = String::new();
-
-    print!("{} ", prompt);
-    let _ = io::stdout().flush().expect("Failed to flush stdout.");
+fn into_cat(mimic: MimicCatSelector) -> CatSelector {
+    match mimic {
+        MimicCatSelector::CatSelectorNone => CatSelector::None,
+        MimicCatSelector::CatSelectorUnicolor { inner } => CatSelector::Unicolor {
+            name: inner.instance.f0,
+            color: inner.instance.f1,
+        },
+        MimicCatSelector::CatSelectorMixed { inner } => CatSelector::Mixed {
+            name: inner.instance.f0,
+            with_stripes: inner.instance.f1,
+        },
+        MimicCatSelector::CatSelectorTabby { inner } => CatSelector::Tabby {
+            fluff_ratio: inner.instance.f0,
+            pattern: inner.instance.f1,
+        },
+        MimicCatSelector::CatSelectorChungus { inner } => CatSelector::Chungus {
+            chung_ratio: inner.instance.f0,
+        },
+    }
+}
 
-    io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read input.");
+/// The variants offered in the menu, as their index in the declaration, leaving out those without fields such as
+/// `None`, which make no cat
+fn cat_choices() -> Vec<usize> {
+    MimicCatSelector::metadata()
+        .iter()
+        .enumerate()
+        .filter(|(_, meta)| !meta.fields.is_empty())
+        .map(|(index, _)| index)
+        .collect()
+}
 
-    input.trim().to_owned()
+/// Ask which cat to make and then for each of its fields, or `None` when no cat is selected or the input ends
+fn select_cat<I: MimicInput>(input: &mut I) -> Result<Option<CatSelector>, &'static str> {
+    let choices = cat_choices();
+    let prompt = format!("Select a Cat (1-{}):", choices.len());
+    let line = match input.read_line(&prompt)? {
+        Some(line) => line,
+        None => return Ok(None),
+    };
+    // Choices are numbered from 1
+    let choice = line
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1));
+    let index = match choice {
+        Some(choice) if choice < choices.len() => choices[choice],
+        _ => return Ok(None),
+    };
+
+    let meta = &MimicCatSelector::metadata()[index];
+    let responses = match prompt_mimic_fields(meta, input)? {
+        Some(responses) => responses,
+        None => return Ok(None),
+    };
+    let responses = responses
+        .iter()
+        .map(|response| response.trim().to_owned())
+        .collect::<Vec<String>>();
+
+    MimicCatSelector::from_index(index, responses).map(|mimic| Some(into_cat(mimic)))
 }
 
 fn main() {
-    println!(
-        "Selections:
-    1. Unicolor
-    2. Mixed
-    3. Tabby
-    4. Chungus"
-    );
-    match get_input("Select a Cat (1-4):").parse::<usize>() {
-        Ok(n @ 1) | Ok(n @ 2) | Ok(n @ 3) | Ok(n @ 4) => {
-            match n {
-                1 => {
-                    let responses = vec![
-                        get_input("Enter the cats name:"),
-                        get_input("Enter the cats color (get creative):"),
-                    ];
-                    let mimic = MimicCatSelector::CatSelectorUnicolor {
-                        inner: CatSelectorUnicolor::try_from(responses)
-                            .expect("Input not recognized for a unicolor cat"),
-                    };
-
-                    if let MimicCatSelector::CatSelectorUnicolor { inner } = mimic {
-                        let cat = CatSelector::Unicolor {
-                            name: inner.instance.f0,
-                            color: inner.instance.f1,
-                        };
-
-                        println!("{:?}", cat);
-                    };
-                }
-                2 => {
-                    let responses = vec![
-                        get_input("Enter the cats name:"),
-                        get_input("Does this cat have stripes? (true/false) "),
-                    ];
-                    let mimic = MimicCatSelector::CatSelectorMixed {
-                        inner: CatSelectorMixed::try_from(responses)
-                            .expect("Input not recognized for a mixed cat"),
-                    };
-
-                    if let MimicCatSelector::CatSelectorMixed { inner } = mimic {
-                        let cat = CatSelector::Mixed {
-                            name: inner.instance.f0,
-                            with_stripes: inner.instance.f1,
-                        };
-
-                        println!("{:?}", cat);
-                    };
-                }
-                3 => {
-                    let responses = vec![get_input("How fluffy is this cat? (A decimal):")];
-                    let mimic = MimicCatSelector::CatSelectorTabby {
-                        inner: CatSelectorTabby::try_from(responses)
-                            .expect("Input not recognized for a tabby cat"),
-                    };
-
-                    if let MimicCatSelector::CatSelectorTabby { inner } = mimic {
-                        let cat = CatSelector::Tabby {
-                            fluff_ratio: inner.instance.f0,
-                        };
-
-                        println!("{:?}", cat);
-                    };
-                }
-                4 => {
-                    let responses = vec![get_input("Chungus approximation (A positive integer):")];
-                    let mimic = MimicCatSelector::CatSelectorChungus {
-                        inner: CatSelectorChungus::try_from(responses)
-                            .expect("Input not recognized for a chungus cat"),
-                    };
-
-                    if let MimicCatSelector::CatSelectorChungus { inner } = mimic {
-                        let cat = CatSelector::Chungus {
-                            chung_ratio: inner.instance.f0,
-                        };
+    println!("Selections:");
+    let names = MimicCatSelector::variant_names();
+    for (choice, index) in cat_choices().into_iter().enumerate() {
+        println!("    {}. {}", choice + 1, names[index]);
+    }
 
-                        println!("{:?}", cat);
-                    };
-                }
-                _ => {}
-            };
-        }
-        Ok(_) | Err(_) => {}
+    match select_cat(&mut MimicStdio::new()) {
+        Ok(Some(cat)) => println!("{:?}", cat),
+        Ok(None) => {}
+        Err(e) => println!("Input not recognized for a cat: {}", e),
     }
 }
@@ -1,124 +1,337 @@
 use proc_macro2::TokenStream as TokenStream2;
 use proc_macro2::{Ident, Span};
+use std::ops::RangeInclusive;
+
+/// Options given to `#[mimic_arg_n(...)]`
+pub struct MimicArgOptions {
+    pub prefix: String,                  // the family name, as in `MimicArg`
+    pub sep: char,                       // the separator for `FromStr` and `Display`
+    pub serde_path: Option<syn::LitStr>, // derive serde through this path rather than on the `serde` feature
+    pub tuples: bool,                    // implement `MimicTuple` for the tuple of each arity
+}
 
-// ARBITRARY: bound to ensure type lettering contiguously iterates from `A` to `Z` in Unicode
-const MAX_RECURSIVE_DEPTH: usize = 26;
+/// Name the generic type of the `index`th field like a spreadsheet column: `A` through `Z`, then `AA`, `AB`, and so on
+fn generic_name(index: usize) -> String {
+    let mut name = String::new();
+    let mut n = index + 1;
+    while n > 0 {
+        let remainder = (n - 1) % 26;
+        name.insert(0, (b'A' + remainder as u8) as char);
+        n = (n - 1) / 26;
+    }
+    name
+}
 
-pub fn define_mimic_arg_n(n: usize) -> Vec<TokenStream2> {
+pub fn define_mimic_arg_n(
+    arities: RangeInclusive<usize>,
+    options: &MimicArgOptions,
+) -> Vec<TokenStream2> {
     let mut parts: Vec<TokenStream2> = vec![];
+    let prefix = options.prefix.as_str();
+    let sep = options.sep;
+
+    let serde_attrs = match &options.serde_path {
+        Some(path) => {
+            let serialize: syn::Path =
+                syn::parse_str(&format!("{}::Serialize", path.value())).unwrap();
+            let deserialize: syn::Path =
+                syn::parse_str(&format!("{}::Deserialize", path.value())).unwrap();
+            quote! {
+                #[derive(#serialize, #deserialize)]
+                #[serde(crate = #path)]
+            }
+        }
+        None => quote! {
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        },
+    };
 
-    if n > MAX_RECURSIVE_DEPTH {
-        panic!("An enum variant cannot exceed a hard-coded limit of 26 members. PR's are always welcome.");
-    }
-
-    let name_arg0 = format!("MimicArg0");
+    let name_arg0 = format!("{}0", prefix);
     let name_arg0_ident = Ident::new(name_arg0.as_str(), Span::call_site());
 
+    let nonzero_error_msg = format!(
+        "Non-zero list-length provided to {} for conversion TypeFrom<Vec<String>>",
+        name_arg0
+    );
+    let no_args_error_msg = format!("FromStr for {} expects no arguments", name_arg0);
+
+    let mimic_tuple_arg0 = if options.tuples {
+        quote! {
+            impl MimicTuple for () {
+                type Arg = #name_arg0_ident;
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     // Create the Arg0 struct, TryFrom, and FromStr implementations
-    parts.push(quote! {
-        use std::{fmt::Debug, str::FromStr};
+    if *arities.start() == 0 {
+        parts.push(quote! {
+            #[derive(Default)]
+            #serde_attrs
+            pub struct #name_arg0_ident {}
+
+            impl #name_arg0_ident {
+                /// Parse from borrowed arguments, such as `&[&str]` or `&[String]`
+                pub fn try_from_strs<Arg: AsRef<str>>(value: &[Arg]) -> Result<Self, &'static str> {
+                    if value.len() != 0usize {
+                        return Err(#nonzero_error_msg);
+                    }
+                    return Ok(#name_arg0_ident::default());
+                }
 
-        #[derive(Default)]
-        pub struct #name_arg0_ident {}
+                /// Parse from any iterator of arguments, such as `line.split_whitespace()`, without copying them
+                pub fn try_from_iter<Args>(value: Args) -> Result<Self, &'static str>
+                where
+                    Args: IntoIterator,
+                    Args::Item: AsRef<str>,
+                {
+                    if value.into_iter().next().is_some() {
+                        return Err(#nonzero_error_msg);
+                    }
+                    return Ok(#name_arg0_ident::default());
+                }
+            }
 
-        impl TryFrom<Vec<String>> for #name_arg0_ident {
-            type Error = &'static str;
-            fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
-                if value.len() != 0usize {
-                    return Err("Non-zero list-length provided to MimicArg0 for conversion TypeFrom<Vec<String>>");
+            impl TryFrom<__private::Vec<__private::String>> for #name_arg0_ident {
+                type Error = &'static str;
+                fn try_from(value: __private::Vec<__private::String>) -> Result<Self, Self::Error> {
+                    #name_arg0_ident::try_from_strs(&value)
                 }
-                return Ok(#name_arg0_ident::default());
             }
-        }
 
-        impl FromStr for #name_arg0_ident {
-            type Err = &'static str;
+            impl TryFrom<&[__private::String]> for #name_arg0_ident {
+                type Error = &'static str;
+                fn try_from(value: &[__private::String]) -> Result<Self, Self::Error> {
+                    #name_arg0_ident::try_from_strs(value)
+                }
+            }
 
-            fn from_str(s: &str) -> Result<Self, Self::Err> {
-                if !s.is_empty() {
-                    return Err("FromStr for MimicArg0 expects no arguments");
+            impl TryFrom<[&str; 0]> for #name_arg0_ident {
+                type Error = &'static str;
+                fn try_from(_value: [&str; 0]) -> Result<Self, Self::Error> {
+                    Ok(#name_arg0_ident::default())
                 }
-                return Ok(#name_arg0_ident::default());
             }
-        }
-    });
+
+            impl TryFrom<&[&str]> for #name_arg0_ident {
+                type Error = &'static str;
+                fn try_from(value: &[&str]) -> Result<Self, Self::Error> {
+                    #name_arg0_ident::try_from_strs(value)
+                }
+            }
+
+            impl ::core::str::FromStr for #name_arg0_ident {
+                type Err = &'static str;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    if !s.is_empty() {
+                        return Err(#no_args_error_msg);
+                    }
+                    return Ok(#name_arg0_ident::default());
+                }
+            }
+
+            impl ::core::fmt::Display for #name_arg0_ident {
+                fn fmt(&self, _f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    Ok(())
+                }
+            }
+
+            impl From<#name_arg0_ident> for () {
+                fn from(_: #name_arg0_ident) -> Self {}
+            }
+
+            impl From<()> for #name_arg0_ident {
+                fn from(_: ()) -> Self {
+                    #name_arg0_ident::default()
+                }
+            }
+
+            #mimic_tuple_arg0
+        });
+    }
 
     // Create the Arg1 through ArgN structs, TryFrom, and FromStr implementations
-    (1..=n).for_each(|i| {
-        let name = format!("MimicArg{}", i);
+    arities.filter(|i| *i > 0).for_each(|i| {
+        let name = format!("{}{}", prefix, i);
         let name_ident = Ident::new(name.as_str(), Span::call_site());
         let args_error_msg = format!("Insufficient list length provided to {} for conversion TryFrom<Vec<String>>", name);
 
-        let generics_as_chars = (0..i)
-            .map(|j| {
-                // Unwraps are safe because 'A'+i is always convertible to base 10 in unicode with a limit on n of 26
-                char::from_u32('A' as u32 + j as u32).unwrap()
-            })
-            .collect::<Vec<char>>();
+        let generic_names = (0..i).map(generic_name).collect::<Vec<String>>();
 
-        let generics_as_markers = generics_as_chars
+        let generics_as_markers = generic_names
             .iter()
-            .map(|c| Ident::new(c.to_string().as_str(), Span::call_site()))
+            .map(|c| Ident::new(c.as_str(), Span::call_site()))
             .collect::<Vec<Ident>>();
 
         let item_list = (0..i)
             .map(|j| Ident::new(format!("f{}", j).as_str(), Span::call_site()))
             .collect::<Vec<Ident>>();
 
-        let nested_tryfrom_parsing_calls = generate_recursive_tryfrom_parsing_calls(i, i, &item_list, &generics_as_chars, &generics_as_markers, &name);
-        let listed_fromstr_parsing_calls = generate_recursive_fromstr_parsing_calls( i, &item_list, &generics_as_chars, &generics_as_markers);
+        let listed_fromstr_parsing_calls = generate_fromstr_parsing_calls(&item_list, &generic_names, &generics_as_markers, &name);
 
         let no_csv_error_msg = format!(
-            "No comma-separated list found in argument to `from_str` for MimicArg{}",
-            i,
+            "No `{}`-separated list found in argument to `from_str` for {}",
+            sep, name,
         );
         let no_csv_error_msg_str = no_csv_error_msg.as_str();
 
         let invalid_argn_error_msg = format!(
-            "Invalid number of CSV arguments provided to `from_str` for MimicArg{}",
-            i,
+            "Invalid number of `{}`-separated arguments provided to `from_str` for {}",
+            sep, name,
         );
         let invalid_argn_error_msg_str = invalid_argn_error_msg.as_str();
 
+        // The last field of `try_from_rest` takes every argument from its position onward
+        let leading_count = i - 1;
+        let leading_markers = &generics_as_markers[..leading_count];
+        let leading_items = &item_list[..leading_count];
+        let leading_indices = 0..leading_count;
+        let last_marker = &generics_as_markers[leading_count];
+        let last_item = &item_list[leading_count];
+        let generics_csv = generic_names.join(",");
+        let parse_error_msgs = generic_names
+            .iter()
+            .map(|c| format!("Failed to parse as `{}` in {}<{}>", c, name, generics_csv))
+            .collect::<Vec<String>>();
+        let leading_error_msgs = &parse_error_msgs[..leading_count];
+        // The `Mk` prefix keeps these apart from the field generics, which are bare letters such as `A` or `AB`
+        let marker_kinds = generic_names
+            .iter()
+            .map(|c| Ident::new(format!("Mk{}", c).as_str(), Span::call_site()))
+            .collect::<Vec<Ident>>();
+        let all_indices = (0..i).collect::<Vec<usize>>();
+        let iter_error_msg = format!(
+            "Mismatched number of arguments provided to {} for conversion try_from_iter",
+            name
+        );
+        let rest_error_msg = format!("Insufficient list length provided to {} for conversion try_from_rest", name);
+        let sep_str = sep.to_string();
+
+        let mimic_tuple = if options.tuples {
+            quote! {
+                impl<#(#generics_as_markers),*> MimicTuple for (#(#generics_as_markers,)*)
+                where
+                    #(#generics_as_markers: ::core::str::FromStr),*
+                {
+                    type Arg = #name_ident<#(#generics_as_markers),*>;
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         parts.push(quote! {
             #[derive(Default)]
+            #serde_attrs
             pub struct #name_ident<#(#generics_as_markers),*> {
                 #(pub #item_list: #generics_as_markers),*
             }
 
-            impl<#(#generics_as_markers),*> TryFrom<Vec<String>> for #name_ident<#(#generics_as_markers),*>
+            impl<#(#generics_as_markers),*> #name_ident<#(#generics_as_markers),*>
             where
-                #(#generics_as_markers: Debug + FromStr),*
+                #(#generics_as_markers: ::core::str::FromStr),*
             {
-                type Error = &'static str;
-
-                fn try_from(input: Vec<String>) -> Result<Self, Self::Error> {
+                /// Parse from borrowed arguments, such as `&[&str]` or `&[String]`
+                pub fn try_from_strs<Arg: AsRef<str>>(input: &[Arg]) -> Result<Self, &'static str> {
                     if input.len() != #i {
                         return Err(#args_error_msg);
                     }
 
-                    #(#nested_tryfrom_parsing_calls)*
+                    Ok(#name_ident {
+                        #(#item_list: input[#all_indices].as_ref().parse::<#generics_as_markers>().or(Err(#parse_error_msgs))?,)*
+                    })
+                }
+
+                /// Parse from any iterator of arguments, such as `line.split_whitespace()`, without copying them
+                pub fn try_from_iter<Args>(input: Args) -> Result<Self, &'static str>
+                where
+                    Args: IntoIterator,
+                    Args::Item: AsRef<str>,
+                {
+                    let mut input = input.into_iter();
+                    let parsed = #name_ident {
+                        #(#item_list: input
+                            .next()
+                            .ok_or(#iter_error_msg)?
+                            .as_ref()
+                            .parse::<#generics_as_markers>()
+                            .or(Err(#parse_error_msgs))?,)*
+                    };
+                    if input.next().is_some() {
+                        return Err(#iter_error_msg);
+                    }
+                    Ok(parsed)
+                }
+            }
+
+            impl<#(#generics_as_markers),*> TryFrom<__private::Vec<__private::String>> for #name_ident<#(#generics_as_markers),*>
+            where
+                #(#generics_as_markers: ::core::str::FromStr),*
+            {
+                type Error = &'static str;
+
+                fn try_from(input: __private::Vec<__private::String>) -> Result<Self, Self::Error> {
+                    #name_ident::try_from_strs(&input)
                 }
             }
 
-            impl<#(#generics_as_markers),*> FromStr for #name_ident<#(#generics_as_markers),*>
+            impl<#(#generics_as_markers),*> TryFrom<&[__private::String]> for #name_ident<#(#generics_as_markers),*>
             where
-                #(#generics_as_markers: Debug + FromStr),*
+                #(#generics_as_markers: ::core::str::FromStr),*
+            {
+                type Error = &'static str;
+
+                fn try_from(input: &[__private::String]) -> Result<Self, Self::Error> {
+                    #name_ident::try_from_strs(input)
+                }
+            }
+
+            // Arrays are only accepted by the `MimicArgN` of matching length, so that their arity is checked at compile time
+            impl<#(#generics_as_markers),*> TryFrom<[&str; #i]> for #name_ident<#(#generics_as_markers),*>
+            where
+                #(#generics_as_markers: ::core::str::FromStr),*
+            {
+                type Error = &'static str;
+
+                fn try_from(input: [&str; #i]) -> Result<Self, Self::Error> {
+                    #name_ident::try_from_strs(&input)
+                }
+            }
+
+            impl<#(#generics_as_markers),*> TryFrom<&[&str; #i]> for #name_ident<#(#generics_as_markers),*>
+            where
+                #(#generics_as_markers: ::core::str::FromStr),*
+            {
+                type Error = &'static str;
+
+                fn try_from(input: &[&str; #i]) -> Result<Self, Self::Error> {
+                    #name_ident::try_from_strs(input)
+                }
+            }
+
+            impl<#(#generics_as_markers),*> TryFrom<&[&str]> for #name_ident<#(#generics_as_markers),*>
+            where
+                #(#generics_as_markers: ::core::str::FromStr),*
+            {
+                type Error = &'static str;
+
+                fn try_from(input: &[&str]) -> Result<Self, Self::Error> {
+                    #name_ident::try_from_strs(input)
+                }
+            }
+
+            impl<#(#generics_as_markers),*> ::core::str::FromStr for #name_ident<#(#generics_as_markers),*>
+            where
+                #(#generics_as_markers: ::core::str::FromStr),*
             {
                 type Err = &'static str;
 
                 fn from_str(input: &str) -> Result<Self, Self::Err> {
-                    let csv = input.split(",")
-                    .filter_map(|s: &str| {
-                        let t = s.trim();
-
-                        if !t.is_empty() {
-                            Some(t)
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<&str>>();
+                    // Empty segments keep their position, so that a missing value is reported where it is missing
+                    let csv = split_mimic_segments(input, #sep)?;
                     if csv.is_empty() {
                         return Err(#no_csv_error_msg_str);
                     }
@@ -130,113 +343,100 @@ pub fn define_mimic_arg_n(n: usize) -> Vec<TokenStream2> {
                     })
                 }
             }
-        });
-    });
 
-    parts
-}
-
-fn generate_recursive_tryfrom_parsing_calls(
-    total: usize,
-    n: usize,
-    item_list: &Vec<Ident>,
-    generics_as_chars: &Vec<char>,
-    generics_as_markers: &Vec<Ident>,
-    struct_name: &String,
-) -> Vec<TokenStream2> {
-    let mut output_stream2 = vec![];
-    if item_list.len() < total {
-        return output_stream2;
-    }
+            impl<#(#generics_as_markers),*> #name_ident<#(#generics_as_markers),*> {
+                /// Like `try_from_strs`, except that fields such as `&'a str` or `Cow<'a, str>` borrow from the
+                /// arguments rather than copy them
+                pub fn try_from_borrowed<'a, #(#marker_kinds),*>(input: &[&'a str]) -> Result<Self, &'static str>
+                where
+                    #(#generics_as_markers: MimicFromArg<'a, #marker_kinds>),*
+                {
+                    if input.len() != #i {
+                        return Err(#args_error_msg);
+                    }
 
-    if n == 0 {
-        fn base_case(struct_name_ident: &Ident, item_list: &Vec<Ident>) -> Vec<TokenStream2> {
-            vec![quote! {
-                return Ok(#struct_name_ident {#(#item_list),*});
-            }]
-        }
+                    Ok(#name_ident {
+                        #(#item_list: <#generics_as_markers as MimicFromArg<'a, #marker_kinds>>::from_arg(input[#all_indices])
+                            .or(Err(#parse_error_msgs))?,)*
+                    })
+                }
+            }
 
-        let struct_name_ident = Ident::new(struct_name.as_str(), Span::call_site());
+            impl<#(#generics_as_markers),*> #name_ident<#(#generics_as_markers),*>
+            where
+                #(#leading_markers: ::core::str::FromStr,)*
+                #last_marker: MimicRest,
+            {
+                /// Like `TryFrom<Vec<String>>`, except that the last field takes every argument from its position
+                /// onward, so there may be as few as one argument less than there are fields
+                pub fn try_from_rest(input: __private::Vec<__private::String>) -> Result<Self, &'static str> {
+                    if input.len() < #leading_count {
+                        return Err(#rest_error_msg);
+                    }
 
-        let instantiate_arg_token_stream = base_case(&struct_name_ident, item_list);
-        output_stream2.extend(instantiate_arg_token_stream)
-    } else if n >= 1 {
-        let list_index = total - n;
-        let item_in_list = &item_list[list_index];
-        let generic_in_list = &generics_as_markers[list_index];
+                    Ok(#name_ident {
+                        #(#leading_items: input[#leading_indices].parse::<#leading_markers>().or(Err(#leading_error_msgs))?,)*
+                        #last_item: <#last_marker as MimicRest>::from_rest(&input[#leading_count..])?,
+                    })
+                }
+            }
 
-        let generics_list = generics_as_chars
-            .iter()
-            .map(|c| c.to_string())
-            .collect::<Vec<String>>();
-        let generics_csv = generics_list.join(",");
-        let error_msg = format!(
-            "Failed to parse as `{}` in {}<{}>",
-            generics_list[list_index], struct_name, generics_csv
-        );
+            impl<#(#generics_as_markers),*> ::core::fmt::Display for #name_ident<#(#generics_as_markers),*>
+            where
+                #(#generics_as_markers: ::core::fmt::Display),*
+            {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    let csv: __private::Vec<__private::String> = __private::vec![#(quote_mimic_segment(&__private::ToString::to_string(&self.#item_list), #sep)),*];
+                    write!(f, "{}", csv.join(#sep_str))
+                }
+            }
 
-        let nested_token_stream = generate_recursive_tryfrom_parsing_calls(
-            total,
-            n - 1,
-            item_list,
-            generics_as_chars,
-            generics_as_markers,
-            struct_name,
-        );
+            impl<#(#generics_as_markers),*> From<#name_ident<#(#generics_as_markers),*>> for (#(#generics_as_markers,)*) {
+                fn from(arg: #name_ident<#(#generics_as_markers),*>) -> Self {
+                    (#(arg.#item_list,)*)
+                }
+            }
 
-        output_stream2.push(quote! {
-            if let Ok(#item_in_list) = input[#list_index].parse::<#generic_in_list>() {
-                #(#nested_token_stream)*
+            impl<#(#generics_as_markers),*> From<(#(#generics_as_markers,)*)> for #name_ident<#(#generics_as_markers),*> {
+                fn from((#(#item_list,)*): (#(#generics_as_markers,)*)) -> Self {
+                    #name_ident { #(#item_list),* }
+                }
             }
-            return Err(#error_msg);
+
+            #mimic_tuple
         });
-    }
+    });
 
-    output_stream2
+    parts
 }
 
-fn generate_recursive_fromstr_parsing_calls(
-    i: usize,
-    item_list: &Vec<Ident>,
-    generics_as_chars: &Vec<char>,
-    generics_as_markers: &Vec<Ident>,
+fn generate_fromstr_parsing_calls(
+    item_list: &[Ident],
+    generic_names: &[String],
+    generics_as_markers: &[Ident],
+    struct_name: &str,
 ) -> Vec<TokenStream2> {
-    let mut output_stream2: Vec<TokenStream2> = vec![];
-    if i == 0 {
-        return output_stream2;
-    }
-
-    if i > item_list.len() || i > generics_as_markers.len() {
-        panic!(
-            "FromStr generation unexpected list lengths i={} argn={} num_markers={}",
-            i,
-            item_list.len(),
-            generics_as_markers.len()
-        );
-    }
-
-    let index = i - 1;
-    let argname = &item_list[index];
-    let marker = &generics_as_markers[index];
-
-    output_stream2.extend(generate_recursive_fromstr_parsing_calls(
-        i - 1,
-        item_list,
-        generics_as_chars,
-        generics_as_markers,
-    ));
-
-    let error_msg = format!(
-        "Failed to parse argument {} as generic type {} in MimicArg{}",
-        index,
-        generics_as_chars[index],
-        item_list.len()
-    );
-    let error_msg_str = error_msg.as_str();
-
-    output_stream2.push(quote! {
-        #argname: csv[#index].parse::<#marker>().map_err(|_| #error_msg_str)?,
-    });
-
-    output_stream2
+    item_list
+        .iter()
+        .zip(generics_as_markers)
+        .enumerate()
+        .map(|(index, (argname, marker))| {
+            let error_msg = format!(
+                "Failed to parse argument {} as generic type {} in {}",
+                index, generic_names[index], struct_name
+            );
+            let missing_error_msg = format!(
+                "Missing argument {} for generic type {} in {}",
+                index, generic_names[index], struct_name
+            );
+
+            quote! {
+                #argname: csv[#index]
+                    .as_deref()
+                    .ok_or(#missing_error_msg)?
+                    .parse::<#marker>()
+                    .or(Err(#error_msg))?,
+            }
+        })
+        .collect()
 }
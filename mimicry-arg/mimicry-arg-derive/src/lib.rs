@@ -4,15 +4,94 @@ extern crate syn;
 extern crate quote;
 
 use proc_macro::TokenStream;
-use syn::LitInt;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Expr, ExprLit, ExprRange, ItemStruct, Lit, RangeLimits, Token};
 
 mod args;
 
+fn parse_arity(expr: &Expr) -> usize {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(arity), .. }) => arity.base10_parse().unwrap(),
+        _ => panic!("Expected a number of arguments such as `#[mimic_arg_n(26)]` or `#[mimic_arg_n(27..=40)]`"),
+    }
+}
+
+fn parse_str_option(expr: &Expr) -> syn::LitStr {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => s.clone(),
+        _ => panic!("Expected a string such as `#[mimic_arg_n(26, sep = \"|\")]`"),
+    }
+}
+
+/// Defines a family of `MimicArg0` through `MimicArgN` structures, as in `#[mimic_arg_n(26)] struct _MimicArg;`.
+///
+/// Any number of arguments is supported, and a range such as `#[mimic_arg_n(27..=40)]` defines only those arities.
+///
+/// The family is named after the annotated structure without its leading underscore, so that several families may be
+/// defined with different separators for `FromStr` and `Display`, as in
+/// `#[mimic_arg_n(4, sep = "|")] struct _MimicPipeArg;`. The separator defaults to `,`. Giving
+/// `serde = "::mimicry::serde"` derives serde's traits through that path instead of on the crate's `serde` feature.
+///
+/// Each `MimicArgN` converts to and from the tuple of its field types. Giving `tuples` also implements `MimicTuple` for
+/// those tuples, which only one family in scope of `MimicTuple` may do.
+///
+/// The generated code expects `MimicRest`, `MimicFromArg`, `split_mimic_segments`, `quote_mimic_segment`, and the hidden
+/// `__private` module to be in scope, as they are through `use mimicry::*`. It only names `core` and `alloc` items, so
+/// that it builds without `std`.
 #[proc_macro_attribute]
-pub fn mimic_arg_n(attr: TokenStream, _item: TokenStream) -> TokenStream {
-    let depth_litint = syn::parse_macro_input!(attr as LitInt);
+pub fn mimic_arg_n(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr_args = Punctuated::<Expr, Token![,]>::parse_terminated
+        .parse(attr)
+        .expect("Expected options such as `#[mimic_arg_n(26, sep = \"|\")]`");
+    let item_struct = syn::parse_macro_input!(item as ItemStruct);
 
-    let depth = depth_litint.base10_parse().unwrap();
+    let mut arities = None;
+    let mut options = args::MimicArgOptions {
+        prefix: item_struct
+            .ident
+            .to_string()
+            .trim_start_matches('_')
+            .to_owned(),
+        sep: ',',
+        serde_path: None,
+        tuples: false,
+    };
+    for arg in attr_args {
+        match &arg {
+            Expr::Range(ExprRange {
+                from, limits, to, ..
+            }) => {
+                let start = from.as_deref().map(parse_arity).unwrap_or(0);
+                let end = match (limits, to.as_deref().map(parse_arity)) {
+                    (RangeLimits::Closed(_), Some(end)) => end,
+                    (RangeLimits::HalfOpen(_), Some(end)) => end - 1,
+                    _ => panic!("Expected a bounded range such as `#[mimic_arg_n(27..=40)]`"),
+                };
+                arities = Some(start..=end);
+            }
+            Expr::Assign(assign) => {
+                let name = match &*assign.left {
+                    Expr::Path(path) if path.path.get_ident().is_some() => {
+                        path.path.get_ident().unwrap().to_string()
+                    }
+                    _ => panic!("Unrecognized option in `#[mimic_arg_n(...)]`"),
+                };
+                let value = parse_str_option(&assign.right);
+                match name.as_str() {
+                    "sep" if value.value().chars().count() == 1 => options.sep = value.value().chars().next().unwrap(),
+                    "sep" => panic!("Expected a single-character separator such as `#[mimic_arg_n(26, sep = \"|\")]`"),
+                    "serde" => options.serde_path = Some(value),
+                    _ => panic!("Unrecognized option in `#[mimic_arg_n(...)]`"),
+                }
+            }
+            Expr::Path(path) if path.path.is_ident("tuples") => options.tuples = true,
+            arity => arities = Some(0..=parse_arity(arity)),
+        }
+    }
+    let arities = arities.expect("Expected the number of arguments such as `#[mimic_arg_n(26)]`");
 
     // Define a type-accurate mimic scheme for N arguments.
     //
@@ -31,8 +110,19 @@ pub fn mimic_arg_n(attr: TokenStream, _item: TokenStream) -> TokenStream {
     //          The vector size on TryFrom is checked during run-time and will return a parsing error on misuse.
     //
     //      2. `impl FromStr`
+    //          splitting on `sep` with `split_mimic_segments`, where `""` is an empty value and a bare empty segment is
+    //          a missing one.
+    //
+    //      3. `impl Display`
+    //          the inverse of `FromStr`, writing each field separated by `sep` and quoted as needed.
+    //
+    //      4. `impl From<MimicArg2<R, S>> for (R, S)` and the reverse.
+    //
+    // Along with `try_from_rest`, a relaxed `TryFrom<Vec<String>>` for when the last field is variadic, taking every
+    // remaining argument through `MimicRest`, and `try_from_borrowed`, for fields which borrow from the arguments
+    // through `MimicFromArg`.
     //
-    let tks2_mimic_arg_n = args::define_mimic_arg_n(depth);
+    let tks2_mimic_arg_n = args::define_mimic_arg_n(arities, &options);
 
     let final_token_stream: TokenStream = quote! {
         #(#tks2_mimic_arg_n)*
@@ -1,8 +1,133 @@
-use mimicry_arg_derive::mimic_arg_n;
+#![cfg_attr(not(feature = "std"), no_std)]
 
+pub use mimicry_arg_derive::mimic_arg_n;
+
+extern crate alloc;
 extern crate mimicry_arg_derive;
 
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::str::FromStr;
+
+mod segment;
+
+pub use segment::{quote_mimic_segment, split_mimic_segments};
+
+/// Not public API. The code generated by `mimic_arg_n` and `#[derive(Mimic)]` names `alloc` and `core` items through
+/// here, so that it builds whether or not the crate using it has `std`.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::borrow::{Cow, ToOwned};
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+    pub use alloc::{format, vec};
+    pub use core::{fmt, str::FromStr};
+}
+
+/// A field type which takes every remaining argument, for the variadic last field parsed by `try_from_rest`.
+pub trait MimicRest: Sized {
+    fn from_rest(args: &[String]) -> Result<Self, &'static str>;
+
+    /// The inverse of `from_rest`
+    fn to_rest(&self) -> Vec<String>;
+}
+
+/// Each remaining argument is parsed as one item
+impl<T> MimicRest for Vec<T>
+where
+    T: FromStr + Display,
+{
+    fn from_rest(args: &[String]) -> Result<Self, &'static str> {
+        args.iter()
+            .map(|arg| arg.parse::<T>())
+            .collect::<Result<Vec<T>, _>>()
+            .map_err(|_| "Failed to parse a remaining argument as an item of Vec<T>")
+    }
+
+    fn to_rest(&self) -> Vec<String> {
+        self.iter().map(|item| item.to_string()).collect()
+    }
+}
+
+/// The remaining arguments are joined by single spaces, as the rest of a line of chat would be
+impl MimicRest for String {
+    fn from_rest(args: &[String]) -> Result<Self, &'static str> {
+        Ok(args.join(" "))
+    }
+
+    fn to_rest(&self) -> Vec<String> {
+        if self.is_empty() {
+            vec![]
+        } else {
+            vec![self.clone()]
+        }
+    }
+}
+
+/// A field type which parses from an argument borrowed for `'a`, for `MimicArgN::try_from_borrowed`.
+///
+/// `Marker` is `MimicOwned` for every `FromStr` type and `MimicBorrowed` for `&'a str` and `Cow<'a, str>`, which
+/// borrow from the argument rather than copy it. It is inferred, as only one of the two applies to any type.
+pub trait MimicFromArg<'a, Marker>: Sized {
+    fn from_arg(arg: &'a str) -> Result<Self, &'static str>;
+}
+
+/// Marks the `MimicFromArg` implementation for types which are parsed, and so own their value
+pub struct MimicOwned;
+
+/// Marks the `MimicFromArg` implementation for types which borrow their value from the argument
+pub struct MimicBorrowed;
+
+impl<'a, T> MimicFromArg<'a, MimicOwned> for T
+where
+    T: FromStr,
+{
+    fn from_arg(arg: &'a str) -> Result<Self, &'static str> {
+        arg.parse::<T>()
+            .map_err(|_| "Failed to parse a borrowed argument")
+    }
+}
+
+impl<'a> MimicFromArg<'a, MimicBorrowed> for &'a str {
+    fn from_arg(arg: &'a str) -> Result<Self, &'static str> {
+        Ok(arg)
+    }
+}
+
+impl<'a> MimicFromArg<'a, MimicBorrowed> for Cow<'a, str> {
+    fn from_arg(arg: &'a str) -> Result<Self, &'static str> {
+        Ok(Cow::Borrowed(arg))
+    }
+}
+
+/// A tuple which parses like the `MimicArgN` of the same length, as in `<(u8, String)>::from_mimic_str("1,uno")`, for
+/// when the `MimicArgN` itself is not needed.
+pub trait MimicTuple: Sized {
+    /// The `MimicArgN` holding the same fields
+    type Arg: FromStr<Err = &'static str> + TryFrom<Vec<String>, Error = &'static str> + Into<Self>;
+
+    /// Parse from `,`-separated values, as `MimicArgN::from_str`
+    fn from_mimic_str(s: &str) -> Result<Self, &'static str> {
+        s.parse::<Self::Arg>().map(Into::into)
+    }
+
+    /// Parse from one argument per field, as `MimicArgN::try_from`
+    fn try_from_mimic_args(args: Vec<String>) -> Result<Self, &'static str> {
+        Self::Arg::try_from(args).map(Into::into)
+    }
+}
+
 // View generated code in stdout using
 //      RUSTFLAGS=-Zmacro-backtrace cargo expand
-#[mimic_arg_n(26)]
+//
+// Arities past 16 are opt-in through the `arity-26` feature, since every dependent crate pays for building each one.
+// `#[derive(Mimic)]` defines its own `MimicArgN` for a variant with more fields than are provided here.
+#[mimic_arg_n(16, tuples)]
+struct _MimicArg;
+
+#[cfg(feature = "arity-26")]
+#[mimic_arg_n(17..=26, tuples)]
 struct _MimicArg;
@@ -0,0 +1,123 @@
+// Splitting a delimited string into segments for `FromStr`, and quoting values for `Display`.
+//
+// Segments are trimmed of surrounding whitespace, and empty segments keep their position so that `a,,c` has three
+// segments rather than two. A bare empty segment is a missing value, while `""` is an explicit empty string. A segment
+// starting with a double quote may contain the delimiter, surrounding whitespace, and `"` or `\` escaped with a
+// backslash, while a `"` later in a segment is kept as is, as in `5" tall`. Quotes are kept in the segment unless they
+// wrap all of it, so that a nested value is split again by its own type.
+//
+// A trailing delimiter starts an empty last segment like any other, so that `a,b,` is missing its third value. A
+// list may choose to ignore it.
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Split `input` on `sep` outside of double quotes, as `None` for a missing value or `Some` for a given one.
+///
+/// Input that is empty or only whitespace has no segments.
+pub fn split_mimic_segments(input: &str, sep: char) -> Result<Vec<Option<String>>, &'static str> {
+    if input.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut raw_segments = vec![];
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            // Only a quote opening a segment, or closing one so opened, is quoting
+            '"' if in_quotes || current.trim().is_empty() => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            '\\' if in_quotes => {
+                current.push(c);
+                match chars.next() {
+                    Some(escaped) => current.push(escaped),
+                    None => return Err("Unterminated double quote in delimited input"),
+                }
+            }
+            c if c == sep && !in_quotes => raw_segments.push(core::mem::take(&mut current)),
+            other => current.push(other),
+        }
+    }
+    if in_quotes {
+        return Err("Unterminated double quote in delimited input");
+    }
+    raw_segments.push(current);
+
+    Ok(raw_segments
+        .iter()
+        .map(|s| unquote_mimic_segment(s.trim()))
+        .collect())
+}
+
+/// A segment wrapped in double quotes has them removed along with its escapes
+fn unquote_mimic_segment(segment: &str) -> Option<String> {
+    if segment.is_empty() {
+        return None;
+    }
+
+    let inner = match segment.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(inner) if is_single_quoted(inner) => inner,
+        _ => return Some(segment.to_owned()),
+    };
+
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped @ ('"' | '\\')) => value.push(escaped),
+                Some(other) => {
+                    value.push('\\');
+                    value.push(other);
+                }
+                None => value.push('\\'),
+            },
+            other => value.push(other),
+        }
+    }
+    Some(value)
+}
+
+/// Whether the inside of `"..."` contains no unescaped quote, so that the outer quotes belong together
+fn is_single_quoted(inner: &str) -> bool {
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if chars.next().is_none() {
+                    return false;
+                }
+            }
+            '"' => return false,
+            _ => {}
+        }
+    }
+    true
+}
+
+/// Quote `value` if needed so that `split_mimic_segments` gives it back unchanged as one segment.
+pub fn quote_mimic_segment(value: &str, sep: char) -> String {
+    let needs_quotes =
+        value.is_empty() || value.trim() != value || value.chars().any(|c| c == sep || c == '"');
+    if !needs_quotes {
+        return value.to_owned();
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,258 @@
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, Error, Expr, ExprArray, ExprAssign, ExprLit, ExprRange, ExprUnary, Lit, Path,
+    RangeLimits, Result, Token, UnOp,
+};
+
+/// Options given on the enum itself, ie: `#[mimic(sample)]`
+#[derive(Default)]
+pub struct ContainerAttrs {
+    pub sample: Option<Expr>, // implement `MimicSample` for the generated types, kept to point errors at it
+    pub handler: bool,        // define a trait with a method per variant, and `dispatch` to call it
+}
+
+/// Options given on a variant of the enum, ie: `#[mimic(default)]`
+#[derive(Default)]
+pub struct VariantAttrs {
+    pub default: Option<Expr>, // the mimic enumeration defaults to this variant, kept to point errors at it
+}
+
+/// Options given on a field of a variant, ie: `#[mimic(short = 'n')]` or `#[mimic(nested)]`
+#[derive(Default)]
+pub struct FieldAttrs {
+    pub short: Option<char>, // the single-character flag for the field, as in `-n`
+    pub nested: bool,        // the field's type also derives `Mimic`
+    pub rest: bool,          // the last field takes every remaining argument
+    pub prompt: Option<String>, // the question asked for the field by an interactive front-end
+    pub constraints: FieldConstraints,
+}
+
+/// Checks on a field's value once it has parsed, ie: `#[mimic(range = 0.0..=1.0)]`
+#[derive(Default)]
+pub struct FieldConstraints {
+    pub validate: Option<Path>,    // a `fn(&T) -> Result<(), &'static str>`
+    pub range: Option<ExprRange>,  // the value is contained in the range
+    pub min_len: Option<usize>,    // the value's `MimicLen` is at least this
+    pub max_len: Option<usize>,    // the value's `MimicLen` is at most this
+    pub one_of: Option<Vec<Expr>>, // the value equals one of these
+}
+
+impl FieldConstraints {
+    pub fn is_empty(&self) -> bool {
+        self.validate.is_none()
+            && self.range.is_none()
+            && self.min_len.is_none()
+            && self.max_len.is_none()
+            && self.one_of.is_none()
+    }
+}
+
+/// Collect the comma-separated items of every `#[mimic(...)]` attribute in the list.
+///
+/// Items are parsed as expressions rather than `syn::Meta`, whose values may only be literals, so that a range, a
+/// path, or an array may be given as in `#[mimic(range = 0.0..=1.0)]`. Errors are spanned to the offending attribute
+/// or item, so that the compiler points at it.
+fn mimic_attr_items(attrs: &[Attribute]) -> Result<Vec<Expr>> {
+    let mut items = vec![];
+    for attr in attrs {
+        if !attr.path.is_ident("mimic") {
+            continue;
+        }
+
+        match attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            Ok(list) => items.extend(list),
+            _ => {
+                return Err(Error::new_spanned(
+                    attr,
+                    "Expected a list of options such as `#[mimic(...)]`",
+                ))
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// The name of a bare option such as `sample`
+fn option_flag(item: &Expr) -> Option<String> {
+    match item {
+        Expr::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+        _ => None,
+    }
+}
+
+/// The name and value of an option such as `short = 'n'`
+fn option_value(item: &Expr) -> Option<(String, &Expr)> {
+    match item {
+        Expr::Assign(ExprAssign { left, right, .. }) => {
+            option_flag(left).map(|name| (name, right.as_ref()))
+        }
+        _ => None,
+    }
+}
+
+fn parse_len(value: &Expr, name: &str) -> Result<usize> {
+    match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(len), ..
+        }) => len.base10_parse(),
+        _ => Err(Error::new_spanned(
+            value,
+            format!("Expected a length such as `#[mimic({} = 1)]`", name),
+        )),
+    }
+}
+
+/// The value of a numeric literal such as `1`, `-0.5`, or `(2)`, or `None` for anything else, such as a constant
+fn literal_number(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(n), ..
+        }) => n.base10_parse().ok(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(n), ..
+        }) => n.base10_parse().ok(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_number(expr).map(|n| -n),
+        Expr::Paren(paren) => literal_number(&paren.expr),
+        Expr::Group(group) => literal_number(&group.expr),
+        _ => None,
+    }
+}
+
+/// Whether a range between literals, such as `5..5` or `3..=1`, contains nothing
+fn is_empty_range(range: &ExprRange) -> bool {
+    let from = range.from.as_deref().and_then(literal_number);
+    let to = range.to.as_deref().and_then(literal_number);
+    match (from, to, &range.limits) {
+        (Some(from), Some(to), RangeLimits::HalfOpen(_)) => from >= to,
+        (Some(from), Some(to), RangeLimits::Closed(_)) => from > to,
+        _ => false,
+    }
+}
+
+pub fn parse_container_attrs(attrs: &[Attribute]) -> Result<ContainerAttrs> {
+    let mut container_attrs = ContainerAttrs::default();
+    for item in mimic_attr_items(attrs)? {
+        match option_flag(&item).as_deref() {
+            Some("sample") => container_attrs.sample = Some(item),
+            Some("handler") => container_attrs.handler = true,
+            _ => {
+                return Err(Error::new_spanned(
+                    item,
+                    "Unrecognized option in `#[mimic(...)]` on an enum",
+                ))
+            }
+        }
+    }
+    Ok(container_attrs)
+}
+
+pub fn parse_variant_attrs(attrs: &[Attribute]) -> Result<VariantAttrs> {
+    let mut variant_attrs = VariantAttrs::default();
+    for item in mimic_attr_items(attrs)? {
+        match option_flag(&item).as_deref() {
+            Some("default") => variant_attrs.default = Some(item),
+            _ => {
+                return Err(Error::new_spanned(
+                    item,
+                    "Unrecognized option in `#[mimic(...)]` on an enum variant",
+                ))
+            }
+        }
+    }
+    Ok(variant_attrs)
+}
+
+pub fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs> {
+    let mut field_attrs = FieldAttrs::default();
+    for item in mimic_attr_items(attrs)? {
+        match option_flag(&item).as_deref() {
+            Some("nested") => {
+                field_attrs.nested = true;
+                continue;
+            }
+            Some("rest") => {
+                field_attrs.rest = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        let constraints = &mut field_attrs.constraints;
+        match option_value(&item) {
+            Some((name, value)) if name == "short" => match value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Char(c), ..
+                }) => field_attrs.short = Some(c.value()),
+                _ => {
+                    return Err(Error::new_spanned(
+                        value,
+                        "Expected a character such as `#[mimic(short = 'n')]`",
+                    ))
+                }
+            },
+            Some((name, value)) if name == "prompt" => match value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => field_attrs.prompt = Some(s.value()),
+                _ => {
+                    return Err(Error::new_spanned(
+                        value,
+                        "Expected a string such as `#[mimic(prompt = \"Pick a color:\")]`",
+                    ))
+                }
+            },
+            Some((name, value)) if name == "validate" => match value {
+                Expr::Path(path) => constraints.validate = Some(path.path.clone()),
+                _ => {
+                    return Err(Error::new_spanned(
+                        value,
+                        "Expected a function such as `#[mimic(validate = check_name)]`",
+                    ))
+                }
+            },
+            Some((name, value)) if name == "range" => match value {
+                Expr::Range(range) if is_empty_range(range) => {
+                    return Err(Error::new_spanned(
+                        value,
+                        "Expected a range containing at least one value",
+                    ))
+                }
+                Expr::Range(range) if range.from.is_some() || range.to.is_some() => {
+                    constraints.range = Some(range.clone())
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        value,
+                        "Expected a range such as `#[mimic(range = 0.0..=1.0)]`",
+                    ))
+                }
+            },
+            Some((name, value)) if name == "min_len" => {
+                constraints.min_len = Some(parse_len(value, &name)?)
+            }
+            Some((name, value)) if name == "max_len" => {
+                constraints.max_len = Some(parse_len(value, &name)?)
+            }
+            Some((name, value)) if name == "one_of" => match value {
+                Expr::Array(ExprArray { elems, .. }) if !elems.is_empty() => {
+                    constraints.one_of = Some(elems.iter().cloned().collect())
+                }
+                _ => return Err(Error::new_spanned(
+                    value,
+                    "Expected a list of values such as `#[mimic(one_of = [\"red\", \"black\"])]`",
+                )),
+            },
+            _ => {
+                return Err(Error::new_spanned(
+                    item,
+                    "Unrecognized option in `#[mimic(...)]` on a field",
+                ))
+            }
+        }
+    }
+    Ok(field_attrs)
+}
@@ -9,9 +9,12 @@ use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use proc_macro2::{Ident, Span};
 
+use syn::spanned::Spanned;
 use syn::Variant;
 use syn::{DataEnum, DeriveInput, Field, PathArguments::AngleBracketed};
 
+mod attrs;
+
 fn extract_type(ty: &syn::Type) -> String {
     match ty {
         syn::Type::Path(type_path) => {
@@ -33,6 +36,10 @@ fn extract_type(ty: &syn::Type) -> String {
             }
             return base_type;
         }
+        // A borrowed field such as `&'a str`
+        syn::Type::Reference(type_reference) => {
+            return format!("&{}", extract_type(&type_reference.elem));
+        }
         _ => {}
     }
 
@@ -59,14 +66,70 @@ struct FieldParts {
     name: String,
     outer_type: String,
     inner_type_list: Vec<String>,
+    short: Option<char>,
+    nested: bool,
+    rest: bool,     // the last field, taking every remaining argument
+    borrowed: bool, // the field borrows from the arguments, as `&'a str` or `Cow<'a, str>` do
+    prompt: String, // the question asked for the field, ie: `name (String)` unless given
+    constraints: attrs::FieldConstraints,
+    ty: syn::Type,
+    ident: Ident, // the field as declared, which may be a raw identifier such as `r#type`
+}
+
+impl FieldParts {
+    /// The mimic enumeration of a nested field of type `Foo`, which is `MimicFoo`
+    fn nested_mimic_ident(&self) -> Ident {
+        Ident::new(
+            format!("Mimic{}", self.outer_type).as_str(),
+            Span::call_site(),
+        )
+    }
+
+    /// The type held by the mimic structure for this field. A nested field holds its mimic enumeration.
+    fn instance_type(&self) -> TokenStream2 {
+        if self.nested {
+            let nested_mimic_ident = self.nested_mimic_ident();
+            quote! { #nested_mimic_ident }
+        } else {
+            let ty = &self.ty;
+            quote! { #ty }
+        }
+    }
 }
 
 struct VariantMimic {
     name: String,
+    ident: Ident, // the variant as declared, to point errors at it
     fields: Vec<FieldParts>,
+    default_marker: Option<syn::Expr>, // the variant's `#[mimic(default)]`, if it has one
+    lifetime: Option<syn::Lifetime>, // the enumeration's lifetime, when a field borrows from the arguments
+}
+
+/// Whether `ty` is `Cow<'a, str>` for the enumeration's lifetime `'a`
+fn is_borrowed_cow(ty: &syn::Type, enum_lifetime: Option<&syn::Lifetime>) -> bool {
+    let (segment, lifetime) = match (ty, enum_lifetime) {
+        (syn::Type::Path(type_path), Some(lifetime)) => match type_path.path.segments.last() {
+            Some(segment) if segment.ident == "Cow" => (segment, lifetime),
+            _ => return false,
+        },
+        _ => return false,
+    };
+    let args = match &segment.arguments {
+        AngleBracketed(abga) => abga.args.iter().collect::<Vec<&syn::GenericArgument>>(),
+        _ => return false,
+    };
+    match args.as_slice() {
+        [syn::GenericArgument::Lifetime(l), syn::GenericArgument::Type(syn::Type::Path(inner))] => {
+            l == lifetime && inner.path.is_ident("str")
+        }
+        _ => false,
+    }
 }
 
-fn digest_field_into_parts(f: &Field) -> FieldParts {
+fn digest_field_into_parts(
+    f: &Field,
+    enum_lifetime: Option<&syn::Lifetime>,
+) -> syn::Result<FieldParts> {
     let mut inner_type_list = vec![];
     let mut type_string = extract_type(&f.ty);
 
@@ -87,26 +150,94 @@ fn digest_field_into_parts(f: &Field) -> FieldParts {
     }
 
     let field_name = f.ident.as_ref().unwrap().to_string();
+    let field_attrs = attrs::parse_field_attrs(&f.attrs)?;
+    let rest = field_attrs.rest || type_string == "Vec";
+    let borrowed = matches!(f.ty, syn::Type::Reference(_)) || is_borrowed_cow(&f.ty, enum_lifetime);
+    let prompt = field_attrs.prompt.unwrap_or_else(|| {
+        if inner_type_list.is_empty() {
+            format!("{} ({})", field_name, type_string)
+        } else {
+            format!(
+                "{} ({}<{}>)",
+                field_name,
+                type_string,
+                inner_type_list.join(", ")
+            )
+        }
+    });
 
-    FieldParts {
+    Ok(FieldParts {
         name: field_name,
         outer_type: type_string,
         inner_type_list,
-    }
+        short: field_attrs.short,
+        nested: field_attrs.nested,
+        rest,
+        borrowed,
+        prompt,
+        constraints: field_attrs.constraints,
+        ty: f.ty.clone(),
+        ident: f.ident.clone().unwrap(),
+    })
 }
 
-fn generate_mimic_from_variant(v: &Variant) -> VariantMimic {
+fn generate_mimic_from_variant(
+    v: &Variant,
+    enum_lifetime: Option<&syn::Lifetime>,
+) -> syn::Result<VariantMimic> {
     let variant_name = v.ident.to_string();
     let fields = v
         .fields
         .iter()
-        .map(|f| digest_field_into_parts(f))
-        .collect::<Vec<FieldParts>>();
+        .map(|f| digest_field_into_parts(f, enum_lifetime))
+        .collect::<syn::Result<Vec<FieldParts>>>()?;
+    let variant_attrs = attrs::parse_variant_attrs(&v.attrs)?;
+
+    // Only the last field may take every remaining argument
+    if let Some(field) = fields.iter().rev().skip(1).find(|f| f.rest) {
+        return Err(syn::Error::new_spanned(
+            &field.ident,
+            format!(
+                "Field `{}` of variant {} takes the remaining arguments but is not the last field",
+                field.name, variant_name
+            ),
+        ));
+    }
+
+    // Borrowed fields carry the enumeration's lifetime through the mimic structure, and cannot be rearranged into owned
+    // arguments as nested and variadic fields are
+    let lifetime = if let Some(borrowed_field) = fields.iter().find(|f| f.borrowed) {
+        if let Some(field) = fields.iter().find(|f| f.nested || f.rest) {
+            return Err(syn::Error::new_spanned(
+                &field.ident,
+                format!(
+                    "Field `{}` of variant {} cannot be nested or variadic alongside borrowed fields",
+                    field.name, variant_name
+                ),
+            ));
+        }
+        let lifetime = enum_lifetime.ok_or_else(|| {
+            syn::Error::new_spanned(
+                &borrowed_field.ty,
+                format!(
+                    "Variant {} has borrowed fields, so the enumeration must declare their lifetime, as in \
+                     `enum Foo<'a>`",
+                    variant_name
+                ),
+            )
+        })?;
+        Some(lifetime.clone())
+    } else {
+        None
+    };
 
-    VariantMimic {
+    Ok(VariantMimic {
         name: variant_name,
+        ident: v.ident.clone(),
         fields,
-    }
+        default_marker: variant_attrs.default,
+        lifetime,
+    })
 }
 
 fn build_mimic_field_from_parts(mimic_fields: &Vec<FieldParts>) -> Vec<TokenStream2> {
@@ -115,11 +246,29 @@ fn build_mimic_field_from_parts(mimic_fields: &Vec<FieldParts>) -> Vec<TokenStre
         let field_name = &field.name;
         let field_outer_type = &field.outer_type;
         let field_inner_types = &field.inner_type_list;
+        let field_short = match field.short {
+            Some(c) => quote! { Some(#c) },
+            None => quote! { None },
+        };
+        let field_nested = if field.nested {
+            let nested_mimic_ident = field.nested_mimic_ident();
+            quote! { Some(#nested_mimic_ident::metadata) }
+        } else {
+            quote! { None }
+        };
+        let field_rest = field.rest;
+        let field_prompt = &field.prompt;
+        let field_constraints = describe_constraints(&field.constraints);
         let ifd_token_stream = quote! {
              MimicFieldData {
                  name: #field_name,
                  type_: #field_outer_type,
-                type_arguments: vec![#(#field_inner_types,)*],
+                type_arguments: __private::vec![#(#field_inner_types,)*],
+                 short: #field_short,
+                 nested: #field_nested,
+                 rest: #field_rest,
+                 prompt: #field_prompt,
+                 constraints: __private::vec![#(#field_constraints),*],
              }
         };
         ifd_streams.push(ifd_token_stream);
@@ -127,6 +276,229 @@ fn build_mimic_field_from_parts(mimic_fields: &Vec<FieldParts>) -> Vec<TokenStre
     ifd_streams
 }
 
+/// Tokens written without whitespace, as in `0.0..=1.0`, for describing a constraint in usage text
+fn compact_tokens<T: quote::ToTokens>(tokens: &T) -> String {
+    quote! { #tokens }
+        .to_string()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect()
+}
+
+/// A value listed in `one_of`, shown without quotes
+fn describe_value(value: &syn::Expr) -> String {
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) => s.value(),
+        other => compact_tokens(other),
+    }
+}
+
+/// How each constraint is shown in usage text, ie: `within 0.0..=1.0`, `length 1..=32`, or `one of red|black`. A
+/// `validate` function is not described.
+fn describe_constraints(constraints: &attrs::FieldConstraints) -> Vec<String> {
+    let mut descriptions = vec![];
+    if let Some(range) = &constraints.range {
+        descriptions.push(format!("within {}", compact_tokens(range)));
+    }
+    match (constraints.min_len, constraints.max_len) {
+        (Some(min), Some(max)) => descriptions.push(format!("length {}..={}", min, max)),
+        (Some(min), None) => descriptions.push(format!("length at least {}", min)),
+        (None, Some(max)) => descriptions.push(format!("length at most {}", max)),
+        (None, None) => {}
+    }
+    if let Some(values) = &constraints.one_of {
+        let values = values.iter().map(describe_value).collect::<Vec<String>>();
+        descriptions.push(format!("one of {}", values.join("|")));
+    }
+    descriptions
+}
+
+/// Check a field's constraints against `value`, a reference to the parsed field, returning early with an error when
+/// one is not met
+fn generate_constraint_checks(field: &FieldParts, mimic_name: &str) -> TokenStream2 {
+    let constraints = &field.constraints;
+    let mut checks = vec![];
+
+    if let Some(range) = &constraints.range {
+        let error_msg = format!(
+            "Field `{}` of {} is not within {}",
+            field.name,
+            mimic_name,
+            compact_tokens(range)
+        );
+        checks.push(quote! {
+            if !(#range).contains(value) {
+                return Err(#error_msg);
+            }
+        });
+    }
+    if let Some(min_len) = constraints.min_len {
+        let error_msg = format!(
+            "Field `{}` of {} is shorter than {}",
+            field.name, mimic_name, min_len
+        );
+        checks.push(quote! {
+            if MimicLen::mimic_len(value) < #min_len {
+                return Err(#error_msg);
+            }
+        });
+    }
+    if let Some(max_len) = constraints.max_len {
+        let error_msg = format!(
+            "Field `{}` of {} is longer than {}",
+            field.name, mimic_name, max_len
+        );
+        checks.push(quote! {
+            if MimicLen::mimic_len(value) > #max_len {
+                return Err(#error_msg);
+            }
+        });
+    }
+    if let Some(values) = &constraints.one_of {
+        let described = values.iter().map(describe_value).collect::<Vec<String>>();
+        let error_msg = format!(
+            "Field `{}` of {} is not one of {}",
+            field.name,
+            mimic_name,
+            described.join("|")
+        );
+        checks.push(quote! {
+            if ![#(#values),*].iter().any(|allowed| *value == *allowed) {
+                return Err(#error_msg);
+            }
+        });
+    }
+    if let Some(validate) = &constraints.validate {
+        checks.push(quote! {
+            #validate(value)?;
+        });
+    }
+
+    quote! { #(#checks)* }
+}
+
+/// The generic parameters of a generated type, which are `<'a>` when it borrows from the arguments
+fn lifetime_params(lifetime: &Option<syn::Lifetime>) -> TokenStream2 {
+    match lifetime {
+        Some(lifetime) => quote! { <#lifetime> },
+        None => quote! {},
+    }
+}
+
+/// Serde borrows from the input for a field holding a borrowed mimic, rather than requiring it to be owned
+fn generate_serde_borrow_attr(lifetime: &Option<syn::Lifetime>) -> TokenStream2 {
+    if !cfg!(feature = "serde") || lifetime.is_none() {
+        return quote! {};
+    }
+
+    quote! {
+        #[serde(borrow)]
+    }
+}
+
+/// Derive serde's `Serialize` and `Deserialize` on a generated type when the `serde` feature is enabled.
+///
+/// Paths go through the `mimicry` re-export so that the deriving crate does not need its own serde dependency.
+fn generate_serde_attrs() -> TokenStream2 {
+    if !cfg!(feature = "serde") {
+        return quote! {};
+    }
+
+    quote! {
+        #[derive(::mimicry::serde::Serialize, ::mimicry::serde::Deserialize)]
+        #[serde(crate = "::mimicry::serde")]
+    }
+}
+
+// The arities of `MimicArgN` defined by `mimicry-arg`, which are extended through the `arity-26` feature shared by both
+// crates. Variants with more fields get a family of their own.
+const MIMIC_ARG_MAX: usize = if cfg!(feature = "arity-26") { 26 } else { 16 };
+
+/// The hidden module defining `MimicArgN` for a variant with more fields than `mimicry-arg` provides
+fn mimic_arg_module_ident(mimic_name_ident: &Ident) -> Ident {
+    Ident::new(
+        format!(
+            "__mimic_arg_{}",
+            mimic_name_ident.to_string().to_lowercase()
+        )
+        .as_str(),
+        Span::call_site(),
+    )
+}
+
+/// The `MimicArgN` holding a variant's fields, ie: `MimicArg2` or `__mimic_arg_foob::MimicArg30`
+fn mimic_arg_path(mimic_name_ident: &Ident, field_count: usize) -> TokenStream2 {
+    let mimicry_arg_ident = Ident::new(
+        format!("MimicArg{}", field_count).as_str(),
+        Span::call_site(),
+    );
+    if field_count <= MIMIC_ARG_MAX {
+        quote! { #mimicry_arg_ident }
+    } else {
+        let module_ident = mimic_arg_module_ident(mimic_name_ident);
+        quote! { #module_ident::#mimicry_arg_ident }
+    }
+}
+
+/// Define `MimicArgN` for each variant with more fields than `mimicry-arg` provides:
+///
+///  #[doc(hidden)]
+///  mod __mimic_arg_foob {
+///      use ::mimicry::*;
+///
+///      #[::mimicry::mimic_arg_n(30..=30)]
+///      struct _MimicArg;
+///  }
+fn generate_mimic_arg_families(
+    mimics: &Vec<VariantMimic>,
+    input_enum_name_ident: &Ident,
+) -> Vec<TokenStream2> {
+    let mut parts = vec![];
+    for mimic in mimics {
+        let field_count = mimic.fields.len();
+        if field_count <= MIMIC_ARG_MAX {
+            continue;
+        }
+
+        let concatenated_name = format!("{}{}", input_enum_name_ident, mimic.name);
+        let mimic_name_ident = Ident::new(concatenated_name.as_str(), Span::call_site());
+        let module_ident = mimic_arg_module_ident(&mimic_name_ident);
+        let arity = proc_macro2::Literal::usize_unsuffixed(field_count);
+        let serde_option = if cfg!(feature = "serde") {
+            quote! { , serde = "::mimicry::serde" }
+        } else {
+            quote! {}
+        };
+
+        parts.push(quote! {
+            #[doc(hidden)]
+            mod #module_ident {
+                use ::mimicry::*;
+
+                #[::mimicry::mimic_arg_n(#arity..=#arity #serde_option)]
+                struct _MimicArg;
+            }
+        });
+    }
+    parts
+}
+
+/// `MimicMetadata` holds `&'static str`s and so is only ever serialized. When deserializing a mimic struct its
+/// metadata is rebuilt from the generated `metadata()` function instead.
+fn generate_serde_meta_attr(mimic_name_ident: &Ident) -> TokenStream2 {
+    if !cfg!(feature = "serde") {
+        return quote! {};
+    }
+
+    let default_fn = format!("{}::metadata", mimic_name_ident);
+    quote! {
+        #[serde(skip_deserializing, default = #default_fn)]
+    }
+}
+
 fn generate_mimic_struct_for_each_variant(
     mimics: &Vec<VariantMimic>,
     input_enum_name_ident: &Ident,
@@ -140,38 +512,61 @@ fn generate_mimic_struct_for_each_variant(
         let mimic_name_ident = Ident::new(concatenated_name.as_str(), Span::call_site());
 
         let field_count = mimic.fields.len();
-        let field_type_list_ident = mimic
+        let field_type_list = mimic
             .fields
             .iter()
-            .map(|f| Ident::new(f.outer_type.as_str(), Span::call_site()))
-            .collect::<Vec<Ident>>();
+            .map(|f| f.instance_type())
+            .collect::<Vec<TokenStream2>>();
 
-        let mimicry_arg_ident = Ident::new(
-            format!("MimicArg{}", field_count).as_str(),
-            Span::call_site(),
-        );
+        let mimicry_arg_ident = mimic_arg_path(&mimic_name_ident, field_count);
         let mimic_fields = build_mimic_field_from_parts(&mimic.fields);
 
         let item_list = (0..field_count)
             .map(|i| Ident::new(format!("f{}", i).as_str(), Span::call_site()))
             .collect::<Vec<Ident>>();
 
+        // Variadic and borrowed fields cannot be parsed from "0", so they fall back to their own default
+        let field_defaults = mimic
+            .fields
+            .iter()
+            .map(|f| {
+                let field_type = f.instance_type();
+                if f.rest || f.borrowed {
+                    quote! { <#field_type as Default>::default() }
+                } else {
+                    quote! { <#field_type as ::core::str::FromStr>::from_str("0").unwrap() }
+                }
+            })
+            .collect::<Vec<TokenStream2>>();
+
+        let meta_serde_attr = generate_serde_meta_attr(&mimic_name_ident);
+        let serde_attrs = generate_serde_attrs();
+        let serde_borrow_attr = generate_serde_borrow_attr(&mimic.lifetime);
+        let lifetime = lifetime_params(&mimic.lifetime);
+
         // There is some repetition in this code between the three possibilities, but it's for the sake of explicitness.
         if field_count == 0 {
             let part = quote! {
+            #serde_attrs
             pub struct #mimic_name_ident {
+                #meta_serde_attr
                 pub meta: MimicMetadata,
                 pub instance: #mimicry_arg_ident,
             }
+            impl #mimic_name_ident {
+                pub fn metadata() -> MimicMetadata {
+                    MimicMetadata {
+                        name: #input_variant_name,
+                        fields: __private::vec![
+                            #(#mimic_fields, )*
+                        ],
+                    }
+                }
+            }
             impl Default for #mimic_name_ident {
                 fn default() -> Self {
                     #mimic_name_ident {
-                        meta: MimicMetadata {
-                            name: #input_variant_name,
-                            fields: vec![
-                                #(#mimic_fields, )*
-                            ],
-                        },
+                        meta: #mimic_name_ident::metadata(),
                         instance: #mimicry_arg_ident {
                             #(#item_list: "".into()),*
                         }
@@ -181,26 +576,43 @@ fn generate_mimic_struct_for_each_variant(
             };
             parts.push(part);
         } else {
+            // A nested mimic enumeration has no `Default` without a unit or marked variant, so a struct with a nested
+            // field has none either
+            let default_impl = if mimic.fields.iter().any(|f| f.nested) {
+                quote! {}
+            } else {
+                quote! {
+                    impl #lifetime Default for #mimic_name_ident #lifetime {
+                        fn default() -> Self {
+                            #mimic_name_ident {
+                                meta: #mimic_name_ident::metadata(),
+                                instance: #mimicry_arg_ident::< #(#field_type_list),* > {
+                                    #(#item_list: #field_defaults),*
+                                }
+                            }
+                        }
+                    }
+                }
+            };
             let part = quote! {
-            pub struct #mimic_name_ident {
+            #serde_attrs
+            pub struct #mimic_name_ident #lifetime {
+                #meta_serde_attr
                 pub meta: MimicMetadata,
-                pub instance: #mimicry_arg_ident<#(#field_type_list_ident),*>,
-            }
-                impl Default for #mimic_name_ident {
-                    fn default() -> Self {
-                        #mimic_name_ident {
-                            meta: MimicMetadata {
-                                name: #input_variant_name,
-                                fields: vec![
-                                    #(#mimic_fields, )*
-                                ],
-                            },
-                            instance: #mimicry_arg_ident::< #(#field_type_list_ident),* > {
-                                #(#item_list: #field_type_list_ident::from_str("0").unwrap()),*
-                            }
+                #serde_borrow_attr
+                pub instance: #mimicry_arg_ident<#(#field_type_list),*>,
+            }
+                impl #lifetime #mimic_name_ident #lifetime {
+                    pub fn metadata() -> MimicMetadata {
+                        MimicMetadata {
+                            name: #input_variant_name,
+                            fields: __private::vec![
+                                #(#mimic_fields, )*
+                            ],
                         }
                     }
                 }
+                #default_impl
             };
             parts.push(part);
         }
@@ -209,6 +621,11 @@ fn generate_mimic_struct_for_each_variant(
     parts
 }
 
+/// The lifetime of the mimic enumeration, when any of its variants borrow from the arguments
+fn mimic_enum_lifetime(mimics: &Vec<VariantMimic>) -> Option<syn::Lifetime> {
+    mimics.iter().find_map(|m| m.lifetime.clone())
+}
+
 fn generate_mimic_enum(
     mimics: &Vec<VariantMimic>,
     input_enum_name_ident: &Ident,
@@ -219,6 +636,9 @@ fn generate_mimic_enum(
     let mimic_enum_name = format!("Mimic{}", variant_name);
     let mimic_enum_name_ident = Ident::new(mimic_enum_name.as_str(), Span::call_site());
 
+    let enum_lifetime = mimic_enum_lifetime(mimics);
+    let enum_lifetime_params = lifetime_params(&enum_lifetime);
+
     let mut variant_mimic_stream: Vec<TokenStream2> = vec![];
     for mimic in mimics {
         let mimic_name = mimic.name.clone();
@@ -232,38 +652,97 @@ fn generate_mimic_enum(
                 #mimic_name_ident,
             });
         } else {
+            let lifetime = lifetime_params(&mimic.lifetime);
+            let serde_borrow_attr = generate_serde_borrow_attr(&mimic.lifetime);
             variant_mimic_stream.push(quote! {
                 #mimic_name_ident {
-                    inner: #mimic_name_ident,
+                    #serde_borrow_attr
+                    inner: #mimic_name_ident #lifetime,
                 },
             });
         }
     }
 
+    let serde_attrs = generate_serde_attrs();
+
     parts.push(quote! {
-        #[derive(Default)]
-        pub enum #mimic_enum_name_ident {
-            #[default]
+        #serde_attrs
+        pub enum #mimic_enum_name_ident #enum_lifetime_params {
             #(#variant_mimic_stream)*
         }
     });
 
+    // The default variant is the one marked `#[mimic(default)]`, or otherwise the first unit variant. Without either
+    // there is no sensible default, so `Default` is not implemented.
+    let default_mimic = mimics
+        .iter()
+        .find(|m| m.default_marker.is_some())
+        .or_else(|| mimics.iter().find(|m| m.fields.is_empty()));
+
+    if let Some(mimic) = default_mimic {
+        let concatenated_name = format!("{}{}", variant_name, mimic.name);
+        let mimic_name_ident = Ident::new(concatenated_name.as_str(), Span::call_site());
+
+        // A marked variant's fields take their own `Default` rather than the mimic struct's, which parses "0" and so
+        // panics for types such as `bool`. A field type without one is an error pointing at the field.
+        let default_variant = if mimic.fields.is_empty() {
+            quote! { #mimic_enum_name_ident::#mimic_name_ident }
+        } else {
+            let mimicry_arg_ident = mimic_arg_path(&mimic_name_ident, mimic.fields.len());
+            let item_list = (0..mimic.fields.len())
+                .map(|i| Ident::new(format!("f{}", i).as_str(), Span::call_site()))
+                .collect::<Vec<Ident>>();
+            let field_defaults = mimic
+                .fields
+                .iter()
+                .map(|f| quote_spanned! { f.ty.span() => ::core::default::Default::default() })
+                .collect::<Vec<TokenStream2>>();
+            quote! {
+                #mimic_enum_name_ident::#mimic_name_ident {
+                    inner: #mimic_name_ident {
+                        meta: #mimic_name_ident::metadata(),
+                        instance: #mimicry_arg_ident {
+                            #(#item_list: #field_defaults),*
+                        },
+                    },
+                }
+            }
+        };
+
+        parts.push(quote! {
+            impl #enum_lifetime_params Default for #mimic_enum_name_ident #enum_lifetime_params {
+                fn default() -> Self {
+                    #default_variant
+                }
+            }
+        });
+    }
+
     parts
 }
 
 /// Implement `TryFrom<Vec<String>>` for each mimic structure. This is so we can turn a vector of user input as
-/// strings into  each individual mimic struct, assuming it parses cleanly.
+/// strings into  each individual mimic struct, assuming it parses cleanly. A nested field spans its variant name and
+/// that variant's fields, which are grouped into one argument for the nested mimic enumeration's `FromStr`.
+///
+/// Owned arguments are positional, as they carry no quoting to tell a value such as `name=uno` from a named field, and
+/// so parse back `to_mimic_args()`. Lines and borrowed arguments may also name fields as `key=value`.
 ///
 ///  impl TryFrom<Vec<String>> for FooA {
 ///      type Error = &'static str;
 ///
 ///      fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
-///          let mut foo_b = FooB::default();
-///          foo_b.instance = MimicArg2::<usize, String>::try_from(value)?;
-///          Ok(foo_b)
+///          let meta = FooB::metadata();
+///          let value = arrange_mimic_line_args(&meta, value, &[true; 2])?;
+///          Ok(FooB {
+///              meta,
+///              instance: MimicArg2::<usize, String>::try_from(value)?,
+///          })
 ///      }
 /// }
 ///
+/// Borrowed arguments are parsed through `try_from_strs`, `try_from_iter`, and `TryFrom<&[&str]>`, which are only
+/// copied when named, nested, or variadic fields need them rearranged.
 fn generate_mimic_try_from(
     mimics: &Vec<VariantMimic>,
     input_enum_name_ident: &Ident,
@@ -276,25 +755,172 @@ fn generate_mimic_try_from(
         let mimic_name_ident = Ident::new(concatenated_name.as_str(), Span::call_site());
 
         let field_count = mimic.fields.len();
-        let field_type_list_ident = mimic
+        let field_type_list = mimic
             .fields
             .iter()
-            .map(|f| Ident::new(f.outer_type.as_str(), Span::call_site()))
-            .collect::<Vec<Ident>>();
+            .map(|f| f.instance_type())
+            .collect::<Vec<TokenStream2>>();
 
-        let mimicry_arg_ident = Ident::new(
-            format!("MimicArg{}", field_count).as_str(),
-            Span::call_site(),
-        );
+        let mimicry_arg_ident = mimic_arg_path(&mimic_name_ident, field_count);
+
+        // Fields are checked against their constraints once every field has parsed
+        let lifetime = lifetime_params(&mimic.lifetime);
+        let constraint_checks = mimic
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| !f.constraints.is_empty())
+            .map(|(i, f)| {
+                let item = Ident::new(format!("f{}", i).as_str(), Span::call_site());
+                let checks = generate_constraint_checks(f, &concatenated_name);
+                quote! {
+                    let value = &self.instance.#item;
+                    #checks
+                }
+            })
+            .collect::<Vec<TokenStream2>>();
+        parts.push(quote! {
+            impl #lifetime #mimic_name_ident #lifetime {
+                /// Check the constraints given on fields with `#[mimic(...)]`, as parsing does. `Default` and
+                /// deserializing do not check them, so their values may need checking with this.
+                pub fn validate(&self) -> Result<(), &'static str> {
+                    #(#constraint_checks)*
+                    Ok(())
+                }
+            }
+        });
+
+        // A variadic last field relaxes the argument count, and always needs its arguments owned
+        let has_rest = mimic.fields.last().is_some_and(|f| f.rest);
+        let instance_try_from = if has_rest {
+            quote! { #mimicry_arg_ident::<#(#field_type_list),*>::try_from_rest(value)? }
+        } else {
+            quote! { #mimicry_arg_ident::<#(#field_type_list),*>::try_from(value)? }
+        };
+        // A borrowing mimic structure only parses from arguments which outlive it
+        if let Some(lifetime) = &mimic.lifetime {
+            parts.push(quote! {
+                impl<#lifetime> #mimic_name_ident<#lifetime> {
+                    /// Parse from borrowed arguments, such as `&[&str]` or `&[String]`, which the borrowed fields
+                    /// point into
+                    pub fn try_from_strs<Arg: AsRef<str>>(value: &#lifetime [Arg]) -> Result<Self, &'static str> {
+                        let meta = #mimic_name_ident::metadata();
+                        let value = arrange_named_strs(&meta, value)?;
+                        let parsed = #mimic_name_ident {
+                            meta,
+                            instance: #mimicry_arg_ident::<#(#field_type_list),*>::try_from_borrowed(&value)?,
+                        };
+                        parsed.validate()?;
+                        Ok(parsed)
+                    }
+                }
+
+                impl<#lifetime> TryFrom<&#lifetime [__private::String]> for #mimic_name_ident<#lifetime> {
+                    type Error = &'static str;
+
+                    fn try_from(value: &#lifetime [__private::String]) -> Result<Self, Self::Error> {
+                        #mimic_name_ident::try_from_strs(value)
+                    }
+                }
+
+                impl<#lifetime> TryFrom<&#lifetime [&#lifetime str]> for #mimic_name_ident<#lifetime> {
+                    type Error = &'static str;
+
+                    fn try_from(value: &#lifetime [&#lifetime str]) -> Result<Self, Self::Error> {
+                        #mimic_name_ident::try_from_strs(value)
+                    }
+                }
+            });
+            continue;
+        }
+
+        let borrowed_try_from = if has_rest {
+            quote! {
+                #mimic_name_ident::try_from_line_args(value.iter().map(|arg| __private::ToOwned::to_owned(arg.as_ref())).collect(), &[])
+            }
+        } else {
+            quote! {
+                let meta = #mimic_name_ident::metadata();
+                if mimic_args_need_arranging(&meta, value) {
+                    return #mimic_name_ident::try_from_line_args(
+                        value.iter().map(|arg| __private::ToOwned::to_owned(arg.as_ref())).collect(),
+                        &[],
+                    );
+                }
+                let parsed = #mimic_name_ident {
+                    meta,
+                    instance: #mimicry_arg_ident::<#(#field_type_list),*>::try_from_strs(value)?,
+                };
+                parsed.validate()?;
+                Ok(parsed)
+            }
+        };
+
+        parts.push(quote! {
+            impl TryFrom<__private::Vec<__private::String>> for #mimic_name_ident {
+                type Error = &'static str;
+
+                fn try_from(value: __private::Vec<__private::String>) -> Result<Self, Self::Error> {
+                    let literal = __private::vec![true; value.len()];
+                    #mimic_name_ident::try_from_line_args(value, &literal)
+                }
+            }
+
+            impl #mimic_name_ident {
+                /// Parse owned arguments, where `literal` marks those to read positionally even if they look like
+                /// `key=value`, as `split_mimic_line_literals` does for a quoted `=`
+                #[doc(hidden)]
+                pub fn try_from_line_args(value: __private::Vec<__private::String>, literal: &[bool]) -> Result<Self, &'static str> {
+                    let meta = #mimic_name_ident::metadata();
+                    let value = arrange_mimic_line_args(&meta, value, literal)?;
+                    let parsed = #mimic_name_ident {
+                        meta,
+                        instance: #instance_try_from,
+                    };
+                    parsed.validate()?;
+                    Ok(parsed)
+                }
+
+                /// Parse GNU-style flags such as `--name uno -v` alongside positional arguments
+                pub fn try_from_flags(value: __private::Vec<__private::String>) -> Result<Self, &'static str> {
+                    let meta = #mimic_name_ident::metadata();
+                    let value = arrange_flag_args(&meta, value)?;
+                    let parsed = #mimic_name_ident {
+                        meta,
+                        instance: #instance_try_from,
+                    };
+                    parsed.validate()?;
+                    Ok(parsed)
+                }
+
+                /// Parse from borrowed arguments, such as `&[&str]` or `&[String]`
+                pub fn try_from_strs<Arg: AsRef<str>>(value: &[Arg]) -> Result<Self, &'static str> {
+                    #borrowed_try_from
+                }
+
+                /// Parse from any iterator of arguments, such as `line.split_whitespace()`
+                pub fn try_from_iter<Args>(value: Args) -> Result<Self, &'static str>
+                where
+                    Args: IntoIterator,
+                    Args::Item: AsRef<str>,
+                {
+                    #mimic_name_ident::try_from_strs(&value.into_iter().collect::<__private::Vec<Args::Item>>())
+                }
+            }
 
-        parts.push(quote!{
-            impl TryFrom<Vec<String>> for #mimic_name_ident {
+            impl TryFrom<&[__private::String]> for #mimic_name_ident {
                 type Error = &'static str;
 
-                fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
-                    let mut mimic_arg = #mimic_name_ident::default();
-                    mimic_arg.instance = #mimicry_arg_ident::<#(#field_type_list_ident),*>::try_from(value)?;
-                    Ok(mimic_arg)
+                fn try_from(value: &[__private::String]) -> Result<Self, Self::Error> {
+                    #mimic_name_ident::try_from_strs(value)
+                }
+            }
+
+            impl TryFrom<&[&str]> for #mimic_name_ident {
+                type Error = &'static str;
+
+                fn try_from(value: &[&str]) -> Result<Self, Self::Error> {
+                    #mimic_name_ident::try_from_strs(value)
                 }
             }
         });
@@ -302,27 +928,668 @@ fn generate_mimic_try_from(
     parts
 }
 
-fn impl_mimic_for_enum(ast: &DeriveInput) -> TokenStream {
-    let input_enum_name_ident = &ast.ident;
-    let data = &ast.data;
+/// Implement the inverse of `TryFrom<Vec<String>>` for each mimic structure, so that a parsed value can be echoed back
+/// as the arguments which produce it:
+///
+///  impl FooB {
+///      pub fn to_mimic_args(&self) -> Vec<String> {
+///          let mut args: Vec<String> = vec![];
+///          args.push(self.instance.f0.to_string());
+///          args.push(self.instance.f1.to_string());
+///          args
+///      }
+///  }
+///
+/// `Display` writes those arguments as a single quoted line, and `FromStr` splits such a line back into arguments.
+fn generate_mimic_to_args(
+    mimics: &Vec<VariantMimic>,
+    input_enum_name_ident: &Ident,
+) -> Vec<TokenStream2> {
+    let mut parts = vec![];
+    for mimic in mimics {
+        let input_enum_name = input_enum_name_ident.to_string();
+        let input_variant_name = mimic.name.clone();
+        let concatenated_name = format!("{}{}", input_enum_name, input_variant_name);
+        let mimic_name_ident = Ident::new(concatenated_name.as_str(), Span::call_site());
 
-    let mut mimics: Vec<VariantMimic> = vec![];
+        // Nested and variadic fields are flattened into their own arguments rather than quoted as one
+        let field_to_args = mimic
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let item = Ident::new(format!("f{}", i).as_str(), Span::call_site());
+                if f.nested {
+                    quote! { args.extend(self.instance.#item.to_mimic_args()); }
+                } else if f.rest {
+                    quote! { args.extend(MimicRest::to_rest(&self.instance.#item)); }
+                } else {
+                    quote! { args.push(__private::ToString::to_string(&self.instance.#item)); }
+                }
+            })
+            .collect::<Vec<TokenStream2>>();
 
-    // Iterate over the Enum's variants and create a mimic representation of each one
-    match data {
-        syn::Data::Enum(DataEnum { variants, .. }) => {
-            for v in variants {
-                let mimic = generate_mimic_from_variant(v);
-                mimics.push(mimic);
+        let lifetime = lifetime_params(&mimic.lifetime);
+        parts.push(quote! {
+            impl #lifetime #mimic_name_ident #lifetime {
+                pub fn to_mimic_args(&self) -> __private::Vec<__private::String> {
+                    #[allow(unused_mut)]
+                    let mut args: __private::Vec<__private::String> = __private::vec![];
+                    #(#field_to_args)*
+                    args
+                }
+            }
+
+            impl #lifetime ::core::fmt::Display for #mimic_name_ident #lifetime {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    write!(f, "{}", join_mimic_args(&self.to_mimic_args()))
+                }
             }
+        });
+
+        // A line split into owned arguments cannot be borrowed from
+        if mimic.lifetime.is_none() {
+            parts.push(quote! {
+                impl ::core::str::FromStr for #mimic_name_ident {
+                    type Err = &'static str;
+
+                    fn from_str(line: &str) -> Result<Self, Self::Err> {
+                        let (value, literal) = split_mimic_line_literals(line)?;
+                        #mimic_name_ident::try_from_line_args(value, &literal)
+                    }
+                }
+            });
         }
-        _ => unimplemented!(),
-    };
+    }
+    parts
+}
 
-    let mut all_tks2s: Vec<TokenStream2> = vec![];
+/// Parse and format the mimic enumeration as a whole. The first argument names the variant and the rest are handed to
+/// that variant's mimic structure:
+///
+///  impl TryFrom<Vec<String>> for MimicFoo {
+///      type Error = &'static str;
+///
+///      fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
+///          // ["B", "1", "text"] => MimicFoo::FooB { inner: FooB::try_from(vec!["1", "text"])? }
+///      }
+///  }
+///
+/// along with `to_mimic_args`, `Display`, and `FromStr` which behave as they do for the mimic structures.
+fn generate_mimic_enum_dispatch(
+    mimics: &Vec<VariantMimic>,
+    input_enum_name_ident: &Ident,
+) -> Vec<TokenStream2> {
+    let input_enum_name = input_enum_name_ident.to_string();
+    let mimic_enum_name = format!("Mimic{}", input_enum_name);
+    let mimic_enum_name_ident = Ident::new(mimic_enum_name.as_str(), Span::call_site());
 
-    // Take an enum such as:
-    //
+    let no_variant_error_msg = format!("No variant name provided to {}", mimic_enum_name);
+    let unknown_variant_error_msg = format!("Unrecognized variant name provided to {}", mimic_enum_name);
+
+    let mut to_args_arms = vec![];
+    let mut try_from_arms = vec![];
+    let mut try_from_flags_arms = vec![];
+    let mut try_from_strs_arms = vec![];
+    let mut metadata_list = vec![];
+    for mimic in mimics {
+        let variant_name = &mimic.name;
+        let concatenated_name = format!("{}{}", input_enum_name, variant_name);
+        let mimic_name_ident = Ident::new(concatenated_name.as_str(), Span::call_site());
+
+        metadata_list.push(quote! { #mimic_name_ident::metadata() });
+
+        if mimic.fields.is_empty() {
+            to_args_arms.push(quote! {
+                #mimic_enum_name_ident::#mimic_name_ident => __private::vec![__private::String::from(#variant_name)],
+            });
+            try_from_arms.push(quote! {
+                if mimic_name_matches(#variant_name, &variant_name) {
+                    #mimic_name_ident::try_from_line_args(args, literal)?;
+                    return Ok(#mimic_enum_name_ident::#mimic_name_ident);
+                }
+            });
+            try_from_flags_arms.push(quote! {
+                if mimic_name_matches(#variant_name, &variant_name) {
+                    #mimic_name_ident::try_from_flags(args)?;
+                    return Ok(#mimic_enum_name_ident::#mimic_name_ident);
+                }
+            });
+            try_from_strs_arms.push(quote! {
+                if mimic_name_matches(#variant_name, variant_name) {
+                    #mimic_name_ident::try_from_strs(args)?;
+                    return Ok(#mimic_enum_name_ident::#mimic_name_ident);
+                }
+            });
+        } else {
+            to_args_arms.push(quote! {
+                #mimic_enum_name_ident::#mimic_name_ident { inner } => {
+                    let mut args = __private::vec![__private::String::from(#variant_name)];
+                    args.extend(inner.to_mimic_args());
+                    args
+                }
+            });
+            try_from_arms.push(quote! {
+                if mimic_name_matches(#variant_name, &variant_name) {
+                    return Ok(#mimic_enum_name_ident::#mimic_name_ident {
+                        inner: #mimic_name_ident::try_from_line_args(args, literal)?,
+                    });
+                }
+            });
+            try_from_flags_arms.push(quote! {
+                if mimic_name_matches(#variant_name, &variant_name) {
+                    return Ok(#mimic_enum_name_ident::#mimic_name_ident {
+                        inner: #mimic_name_ident::try_from_flags(args)?,
+                    });
+                }
+            });
+            try_from_strs_arms.push(quote! {
+                if mimic_name_matches(#variant_name, variant_name) {
+                    return Ok(#mimic_enum_name_ident::#mimic_name_ident {
+                        inner: #mimic_name_ident::try_from_strs(args)?,
+                    });
+                }
+            });
+        }
+    }
+
+    let enum_lifetime = mimic_enum_lifetime(mimics);
+    let lifetime = lifetime_params(&enum_lifetime);
+
+    let mut parts = vec![quote! {
+        impl #lifetime #mimic_enum_name_ident #lifetime {
+            /// Metadata for every variant, in declaration order
+            pub fn metadata() -> __private::Vec<MimicMetadata> {
+                __private::vec![#(#metadata_list),*]
+            }
+
+            pub fn to_mimic_args(&self) -> __private::Vec<__private::String> {
+                match self {
+                    #(#to_args_arms)*
+                }
+            }
+        }
+
+        impl #lifetime ::core::fmt::Display for #mimic_enum_name_ident #lifetime {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(f, "{}", join_mimic_args(&self.to_mimic_args()))
+            }
+        }
+    }];
+
+    // A borrowing mimic enumeration only parses from arguments which outlive it
+    if let Some(lifetime) = &enum_lifetime {
+        parts.push(quote! {
+            impl<#lifetime> #mimic_enum_name_ident<#lifetime> {
+                /// Parse the variant name followed by its fields from borrowed arguments, such as `&[&str]`, which the
+                /// borrowed fields point into
+                pub fn try_from_strs<Arg: AsRef<str>>(value: &#lifetime [Arg]) -> Result<Self, &'static str> {
+                    let (variant_name, args) = value.split_first().ok_or(#no_variant_error_msg)?;
+                    let variant_name = variant_name.as_ref();
+
+                    #(#try_from_strs_arms)*
+
+                    Err(#unknown_variant_error_msg)
+                }
+            }
+
+            impl<#lifetime> TryFrom<&#lifetime [__private::String]> for #mimic_enum_name_ident<#lifetime> {
+                type Error = &'static str;
+
+                fn try_from(value: &#lifetime [__private::String]) -> Result<Self, Self::Error> {
+                    #mimic_enum_name_ident::try_from_strs(value)
+                }
+            }
+
+            impl<#lifetime> TryFrom<&#lifetime [&#lifetime str]> for #mimic_enum_name_ident<#lifetime> {
+                type Error = &'static str;
+
+                fn try_from(value: &#lifetime [&#lifetime str]) -> Result<Self, Self::Error> {
+                    #mimic_enum_name_ident::try_from_strs(value)
+                }
+            }
+        });
+        return parts;
+    }
+
+    parts.push(quote! {
+        impl #mimic_enum_name_ident {
+            /// Parse the variant name followed by GNU-style flags for its fields, as in
+            /// `connect --name uno --client-version 1.5.2`
+            pub fn try_from_flags(value: __private::Vec<__private::String>) -> Result<Self, &'static str> {
+                let mut value = value.into_iter();
+                let variant_name = value.next().ok_or(#no_variant_error_msg)?;
+                let args = value.collect::<__private::Vec<__private::String>>();
+
+                #(#try_from_flags_arms)*
+
+                Err(#unknown_variant_error_msg)
+            }
+
+            /// Parse the variant name followed by its fields from borrowed arguments, such as `&[&str]`
+            pub fn try_from_strs<Arg: AsRef<str>>(value: &[Arg]) -> Result<Self, &'static str> {
+                let (variant_name, args) = value.split_first().ok_or(#no_variant_error_msg)?;
+                let variant_name = variant_name.as_ref();
+
+                #(#try_from_strs_arms)*
+
+                Err(#unknown_variant_error_msg)
+            }
+
+            /// Parse the variant name followed by its fields from any iterator of arguments, such as
+            /// `line.split_whitespace()`
+            pub fn try_from_iter<Args>(value: Args) -> Result<Self, &'static str>
+            where
+                Args: IntoIterator,
+                Args::Item: AsRef<str>,
+            {
+                #mimic_enum_name_ident::try_from_strs(&value.into_iter().collect::<__private::Vec<Args::Item>>())
+            }
+        }
+
+        impl TryFrom<&[__private::String]> for #mimic_enum_name_ident {
+            type Error = &'static str;
+
+            fn try_from(value: &[__private::String]) -> Result<Self, Self::Error> {
+                #mimic_enum_name_ident::try_from_strs(value)
+            }
+        }
+
+        impl TryFrom<&[&str]> for #mimic_enum_name_ident {
+            type Error = &'static str;
+
+            fn try_from(value: &[&str]) -> Result<Self, Self::Error> {
+                #mimic_enum_name_ident::try_from_strs(value)
+            }
+        }
+
+        impl TryFrom<__private::Vec<__private::String>> for #mimic_enum_name_ident {
+            type Error = &'static str;
+
+            fn try_from(value: __private::Vec<__private::String>) -> Result<Self, Self::Error> {
+                let literal = __private::vec![true; value.len()];
+                #mimic_enum_name_ident::try_from_line_args(value, &literal)
+            }
+        }
+
+        impl #mimic_enum_name_ident {
+            /// Parse the variant name followed by its fields, where `literal` marks the arguments to read positionally
+            /// even if they look like `key=value`, as `split_mimic_line_literals` does for a quoted `=`
+            #[doc(hidden)]
+            pub fn try_from_line_args(value: __private::Vec<__private::String>, literal: &[bool]) -> Result<Self, &'static str> {
+                let mut value = value.into_iter();
+                let variant_name = value.next().ok_or(#no_variant_error_msg)?;
+                let args = value.collect::<__private::Vec<__private::String>>();
+                let literal = literal.get(1..).unwrap_or(&[]);
+
+                #(#try_from_arms)*
+
+                Err(#unknown_variant_error_msg)
+            }
+        }
+
+        impl ::core::str::FromStr for #mimic_enum_name_ident {
+            type Err = &'static str;
+
+            fn from_str(line: &str) -> Result<Self, Self::Err> {
+                let (value, literal) = split_mimic_line_literals(line)?;
+                #mimic_enum_name_ident::try_from_line_args(value, &literal)
+            }
+        }
+    });
+    parts
+}
+
+/// Select variants of the mimic enumeration by their position in the declaration, so that menus can be built from the
+/// enumeration rather than duplicating it:
+///
+///  impl MimicFoo {
+///      pub const fn variant_count() -> usize { 2 }
+///      pub fn variant_names() -> &'static [&'static str] { &["A", "B"] }
+///      pub fn from_index(index: usize, args: Vec<String>) -> Result<Self, &'static str> {
+///          // 1 => MimicFoo::FooB { inner: FooB::try_from(args)? }
+///      }
+///  }
+///
+fn generate_mimic_variant_index(
+    mimics: &Vec<VariantMimic>,
+    input_enum_name_ident: &Ident,
+) -> Vec<TokenStream2> {
+    let input_enum_name = input_enum_name_ident.to_string();
+    let mimic_enum_name = format!("Mimic{}", input_enum_name);
+    let mimic_enum_name_ident = Ident::new(mimic_enum_name.as_str(), Span::call_site());
+
+    let out_of_range_error_msg = format!("Variant index out of range for {}", mimic_enum_name);
+
+    let variant_count = mimics.len();
+    let variant_names = mimics
+        .iter()
+        .map(|m| m.name.clone())
+        .collect::<Vec<String>>();
+
+    let mut from_index_arms = vec![];
+    for (index, mimic) in mimics.iter().enumerate() {
+        let concatenated_name = format!("{}{}", input_enum_name, mimic.name);
+        let mimic_name_ident = Ident::new(concatenated_name.as_str(), Span::call_site());
+
+        if mimic.fields.is_empty() {
+            from_index_arms.push(quote! {
+                #index => {
+                    #mimic_name_ident::try_from(args)?;
+                    Ok(#mimic_enum_name_ident::#mimic_name_ident)
+                }
+            });
+        } else {
+            from_index_arms.push(quote! {
+                #index => Ok(#mimic_enum_name_ident::#mimic_name_ident {
+                    inner: #mimic_name_ident::try_from(args)?,
+                }),
+            });
+        }
+    }
+
+    // `from_index` parses owned arguments, which a borrowing mimic enumeration cannot keep
+    if let Some(lifetime) = mimic_enum_lifetime(mimics) {
+        return vec![quote! {
+            impl<#lifetime> #mimic_enum_name_ident<#lifetime> {
+                pub const fn variant_count() -> usize {
+                    #variant_count
+                }
+
+                /// Variant names in declaration order
+                pub fn variant_names() -> &'static [&'static str] {
+                    &[#(#variant_names),*]
+                }
+            }
+        }];
+    }
+
+    vec![quote! {
+        impl #mimic_enum_name_ident {
+            pub const fn variant_count() -> usize {
+                #variant_count
+            }
+
+            /// Variant names in declaration order
+            pub fn variant_names() -> &'static [&'static str] {
+                &[#(#variant_names),*]
+            }
+
+            /// Parse the fields of the variant at `index` in declaration order, counting from zero
+            pub fn from_index(index: usize, args: __private::Vec<__private::String>) -> Result<Self, &'static str> {
+                match index {
+                    #(#from_index_arms)*
+                    _ => Err(#out_of_range_error_msg),
+                }
+            }
+        }
+    }]
+}
+
+/// Implement `MimicEnum` for the mimic enumeration, and with `std` give the original enumeration constructors for it
+/// from the command-line:
+///
+///  impl Foo {
+///      pub fn from_env_args() -> Result<MimicFoo, &'static str> { ... }
+///      pub fn from_args<I: IntoIterator<Item = OsString>>(args: I) -> Result<MimicFoo, &'static str> { ... }
+///  }
+///
+fn generate_mimic_from_args(input_enum_name_ident: &Ident) -> Vec<TokenStream2> {
+    let mimic_enum_name = format!("Mimic{}", input_enum_name_ident);
+    let mimic_enum_name_ident = Ident::new(mimic_enum_name.as_str(), Span::call_site());
+
+    let mut parts = vec![quote! {
+        impl MimicEnum for #mimic_enum_name_ident {
+            fn metadata() -> __private::Vec<MimicMetadata> {
+                #mimic_enum_name_ident::metadata()
+            }
+
+            fn try_from_flags(args: __private::Vec<__private::String>) -> Result<Self, &'static str> {
+                #mimic_enum_name_ident::try_from_flags(args)
+            }
+        }
+    }];
+
+    // The command-line and its usage output are only available with `std`
+    if cfg!(feature = "std") {
+        parts.push(quote! {
+            impl #input_enum_name_ident {
+                /// Parse the arguments this program was run with, printing usage on `--help` or on error
+                pub fn from_env_args() -> Result<#mimic_enum_name_ident, &'static str> {
+                    #input_enum_name_ident::from_args(::std::env::args_os())
+                }
+
+                /// Parse `argv` where the first argument is the program name and the second the variant name, printing
+                /// usage on `--help` or on error
+                pub fn from_args<I>(args: I) -> Result<#mimic_enum_name_ident, &'static str>
+                where
+                    I: IntoIterator<Item = ::std::ffi::OsString>,
+                {
+                    mimic_from_args::<#mimic_enum_name_ident, I>(args)
+                }
+            }
+        });
+    }
+    parts
+}
+
+// ARBITRARY: enough attempts for a constraint met by one sample in a hundred, yet quick to give up on one that is never
+// met
+const SAMPLE_MAX_ATTEMPTS: usize = 1000;
+
+/// A random value of a field which meets its constraints.
+///
+/// A value is drawn from `one_of` when given, and otherwise from within a bounded `range` for numbers. Any other
+/// constraint is met by drawing again until it is, which panics after `SAMPLE_MAX_ATTEMPTS` draws.
+fn generate_field_sample(field: &FieldParts, mimic_name: &str) -> TokenStream2 {
+    let ty = field.instance_type();
+    if field.constraints.is_empty() {
+        return quote! { <#ty as MimicSample>::mimic_sample(rng) };
+    }
+
+    let is_float = ["f32", "f64"].contains(&field.outer_type.as_str());
+    let is_integer = [
+        "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+    ]
+    .contains(&field.outer_type.as_str());
+    let bounded_range = field
+        .constraints
+        .range
+        .as_ref()
+        .and_then(|range| Some((range.from.as_ref()?, range.to.as_ref()?, range)));
+
+    let candidate = match (&field.constraints.one_of, bounded_range) {
+        (Some(values), _) => quote! {
+            {
+                let allowed = [#(#values),*];
+                let pick = __private::ToString::to_string(&allowed[rng.below(allowed.len() as u64) as usize]);
+                match <#ty as ::core::str::FromStr>::from_str(&pick) {
+                    Ok(value) => value,
+                    Err(_) => continue,
+                }
+            }
+        },
+        (None, Some((from, to, _))) if is_float => quote! {
+            {
+                let (low, high): (#ty, #ty) = (#from, #to);
+                low + (high - low) * (rng.next_f64() as #ty)
+            }
+        },
+        // A range of constants may still be empty, which runs out of attempts rather than sampling below zero
+        (None, Some((from, to, range))) if is_integer => {
+            let inclusive = matches!(range.limits, syn::RangeLimits::Closed(_));
+            quote! {
+                {
+                    let (low, high): (#ty, #ty) = (#from, #to);
+                    let span = high as i128 - low as i128 + #inclusive as i128;
+                    if span <= 0 {
+                        continue;
+                    }
+                    (low as i128 + rng.below(span as u64) as i128) as #ty
+                }
+            }
+        }
+        _ => quote! { <#ty as MimicSample>::mimic_sample(rng) },
+    };
+
+    let checks = generate_constraint_checks(field, mimic_name);
+    let error_msg = format!(
+        "Could not sample field `{}` of {} within its constraints",
+        field.name, mimic_name
+    );
+    quote! {
+        {
+            fn meets_constraints(value: &#ty) -> Result<(), &'static str> {
+                #checks
+                Ok(())
+            }
+
+            let mut attempts = 0;
+            loop {
+                attempts += 1;
+                if attempts > #SAMPLE_MAX_ATTEMPTS {
+                    panic!(#error_msg);
+                }
+                let candidate: #ty = #candidate;
+                if meets_constraints(&candidate).is_ok() {
+                    break candidate;
+                }
+            }
+        }
+    }
+}
+
+/// Implement `MimicSample` for each mimic structure and for the mimic enumeration, so that random valid values can be
+/// generated for property testing:
+///
+///  impl MimicSample for FooB {
+///      fn mimic_sample(rng: &mut MimicRng) -> Self {
+///          FooB {
+///              meta: FooB::metadata(),
+///              instance: MimicArg2 { f0: usize::mimic_sample(rng), f1: String::mimic_sample(rng) },
+///          }
+///      }
+///  }
+///
+/// The enumeration also gets `sample_variants`, which yields one sample of every variant paired with its arguments.
+fn generate_mimic_sample(
+    mimics: &Vec<VariantMimic>,
+    input_enum_name_ident: &Ident,
+) -> Vec<TokenStream2> {
+    let mut parts = vec![];
+
+    let input_enum_name = input_enum_name_ident.to_string();
+    let mimic_enum_name = format!("Mimic{}", input_enum_name);
+    let mimic_enum_name_ident = Ident::new(mimic_enum_name.as_str(), Span::call_site());
+
+    let mut variant_samples = vec![];
+    for mimic in mimics {
+        let concatenated_name = format!("{}{}", input_enum_name, mimic.name);
+        let mimic_name_ident = Ident::new(concatenated_name.as_str(), Span::call_site());
+
+        let field_count = mimic.fields.len();
+        let field_samples = mimic
+            .fields
+            .iter()
+            .map(|f| generate_field_sample(f, &concatenated_name))
+            .collect::<Vec<TokenStream2>>();
+
+        let mimicry_arg_ident = mimic_arg_path(&mimic_name_ident, field_count);
+
+        let item_list = (0..field_count)
+            .map(|i| Ident::new(format!("f{}", i).as_str(), Span::call_site()))
+            .collect::<Vec<Ident>>();
+
+        parts.push(quote! {
+            impl MimicSample for #mimic_name_ident {
+                fn mimic_sample(rng: &mut MimicRng) -> Self {
+                    #mimic_name_ident {
+                        meta: #mimic_name_ident::metadata(),
+                        instance: #mimicry_arg_ident {
+                            #(#item_list: #field_samples),*
+                        },
+                    }
+                }
+            }
+        });
+
+        if field_count == 0 {
+            variant_samples.push(quote! { #mimic_enum_name_ident::#mimic_name_ident });
+        } else {
+            variant_samples.push(quote! {
+                #mimic_enum_name_ident::#mimic_name_ident {
+                    inner: #mimic_name_ident::mimic_sample(rng),
+                }
+            });
+        }
+    }
+
+    let variant_count = mimics.len() as u64;
+    let variant_indices = 0..variant_count;
+
+    parts.push(quote! {
+        impl MimicSample for #mimic_enum_name_ident {
+            fn mimic_sample(rng: &mut MimicRng) -> Self {
+                match rng.below(#variant_count) {
+                    #(#variant_indices => #variant_samples,)*
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        impl #mimic_enum_name_ident {
+            /// One random sample of every variant in declaration order, each paired with the arguments that parse
+            /// back into it through `TryFrom<Vec<String>>`
+            pub fn sample_variants(rng: &mut MimicRng) -> __private::Vec<(#mimic_enum_name_ident, __private::Vec<__private::String>)> {
+                __private::vec![#(#variant_samples),*]
+                    .into_iter()
+                    .map(|sample: #mimic_enum_name_ident| {
+                        let args = sample.to_mimic_args();
+                        (sample, args)
+                    })
+                    .collect()
+            }
+        }
+    });
+
+    parts
+}
+
+fn impl_mimic_for_enum(ast: &DeriveInput) -> TokenStream {
+    let input_enum_name_ident = &ast.ident;
+    let data = &ast.data;
+    let container_attrs = match attrs::parse_container_attrs(&ast.attrs) {
+        Ok(container_attrs) => container_attrs,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let enum_lifetime = ast.generics.lifetimes().next().map(|l| &l.lifetime);
+
+    let mut mimics: Vec<VariantMimic> = vec![];
+
+    // Iterate over the Enum's variants and create a mimic representation of each one
+    match data {
+        syn::Data::Enum(DataEnum { variants, .. }) => {
+            for v in variants {
+                match generate_mimic_from_variant(v, enum_lifetime) {
+                    Ok(mimic) => mimics.push(mimic),
+                    Err(e) => return e.to_compile_error().into(),
+                }
+            }
+        }
+        _ => unimplemented!(),
+    };
+
+    // Only one variant may be the default of the mimic enumeration
+    if let Some(marker) = mimics.iter().filter_map(|m| m.default_marker.as_ref()).nth(1) {
+        return syn::Error::new_spanned(marker, "Only one variant may be marked `#[mimic(default)]`")
+            .to_compile_error()
+            .into();
+    }
+
+    let mut all_tks2s: Vec<TokenStream2> = vec![];
+
+    // Take an enum such as:
+    //
     //  #[derive(Mimic)]
     // pub enum Foo {
     //     A,
@@ -353,6 +1620,10 @@ fn impl_mimic_for_enum(ast: &DeriveInput) -> TokenStream {
     //      }
     //  }
 
+    // Variants with more fields than `mimicry-arg` provides for define their own `MimicArgN`
+    let tks2_mimic_arg_families = generate_mimic_arg_families(&mimics, input_enum_name_ident);
+    all_tks2s.extend(tks2_mimic_arg_families);
+
     let tks2_mimic_structs =
         generate_mimic_struct_for_each_variant(&mimics, input_enum_name_ident);
     all_tks2s.extend(tks2_mimic_structs);
@@ -370,6 +1641,46 @@ fn impl_mimic_for_enum(ast: &DeriveInput) -> TokenStream {
     let tks2_try_froms = generate_mimic_try_from(&mimics, input_enum_name_ident);
     all_tks2s.extend(tks2_try_froms);
 
+    // Format each mimic structure back into the arguments that parse into it, and do the same for the enumeration with
+    // the variant name leading:
+    //
+    //  MimicFoo::try_from(vec!["B", "1", "text"])?.to_mimic_args() == vec!["B", "1", "text"]
+    //
+    let tks2_to_args = generate_mimic_to_args(&mimics, input_enum_name_ident);
+    all_tks2s.extend(tks2_to_args);
+
+    let tks2_enum_dispatch = generate_mimic_enum_dispatch(&mimics, input_enum_name_ident);
+    all_tks2s.extend(tks2_enum_dispatch);
+
+    let tks2_variant_index = generate_mimic_variant_index(&mimics, input_enum_name_ident);
+    all_tks2s.extend(tks2_variant_index);
+
+    // Let the mimic enumeration be used generically, such as to parse it straight from `std::env::args_os()`, which a
+    // borrowing mimic enumeration cannot outlive
+    let borrowed = mimic_enum_lifetime(&mimics).is_some();
+    if !borrowed {
+        let tks2_from_args = generate_mimic_from_args(input_enum_name_ident);
+        all_tks2s.extend(tks2_from_args);
+    }
+
+    // Opt-in with `#[mimic(sample)]`, since every field type must then implement `MimicSample`
+    if let Some(sample) = &container_attrs.sample {
+        if borrowed {
+            let msg = "`#[mimic(sample)]` is not supported for an enum with borrowed fields";
+            return syn::Error::new_spanned(sample, msg).to_compile_error().into();
+        }
+        let tks2_samples = generate_mimic_sample(&mimics, input_enum_name_ident);
+        all_tks2s.extend(tks2_samples);
+    }
+
+    // Opt-in with `#[mimic(handler)]`, dispatching each variant to a method taking its fields
+    if container_attrs.handler {
+        match generate_mimic_handler(&mimics, input_enum_name_ident) {
+            Ok(tks2_handler) => all_tks2s.extend(tks2_handler),
+            Err(e) => return e.to_compile_error().into(),
+        }
+    }
+
     let final_token_stream: TokenStream = quote! {
         #(#all_tks2s)*
     }
@@ -378,7 +1689,144 @@ fn impl_mimic_for_enum(ast: &DeriveInput) -> TokenStream {
     final_token_stream
 }
 
-#[proc_macro_derive(Mimic)]
+/// The `snake_case` method name for a variant, as in `drop_pattern` for `DropPattern` or `http_get` for `HTTPGet`
+fn handler_method_name(variant_name: &str) -> String {
+    let chars = variant_name.chars().collect::<Vec<char>>();
+    let mut method_name = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        // A word starts at a capital following a lowercase letter or digit, or at the last capital of an acronym
+        let starts_word = c.is_uppercase()
+            && match i.checked_sub(1).map(|j| chars[j]) {
+                Some(prev) if prev.is_lowercase() || prev.is_ascii_digit() => true,
+                Some(prev) if prev.is_uppercase() => chars.get(i + 1).is_some_and(|next| next.is_lowercase()),
+                _ => false,
+            };
+        if starts_word {
+            method_name.push('_');
+        }
+        method_name.extend(c.to_lowercase());
+    }
+    method_name
+}
+
+/// The method identifier for a variant, which is a raw identifier when its name is a keyword such as `r#move`. The
+/// keywords which cannot be raw identifiers are rejected.
+fn handler_method_ident(mimic: &VariantMimic) -> syn::Result<Ident> {
+    let method_name = handler_method_name(&mimic.name);
+    if let Ok(ident) = syn::parse_str::<Ident>(&method_name) {
+        return Ok(ident);
+    }
+    if matches!(method_name.as_str(), "crate" | "self" | "super" | "Self" | "_") {
+        return Err(syn::Error::new_spanned(
+            &mimic.ident,
+            format!("Variant {} would have the handler method `{}`, which is reserved", mimic.name, method_name),
+        ));
+    }
+    Ok(Ident::new_raw(&method_name, Span::call_site()))
+}
+
+/// Opt-in with `#[mimic(handler)]`, define a trait with a method per variant taking its fields, and have the mimic
+/// enumeration dispatch to it:
+///
+///  pub trait FooHandler {
+///      type Output;
+///      fn a(&mut self) -> Self::Output;
+///      fn b(&mut self, b0: usize, b1: String) -> Self::Output;
+///  }
+///
+///  impl MimicFoo {
+///      pub fn dispatch<H: FooHandler + ?Sized>(self, handler: &mut H) -> H::Output { ... }
+///  }
+///
+fn generate_mimic_handler(
+    mimics: &Vec<VariantMimic>,
+    input_enum_name_ident: &Ident,
+) -> syn::Result<Vec<TokenStream2>> {
+    let input_enum_name = input_enum_name_ident.to_string();
+    let mimic_enum_name = format!("Mimic{}", input_enum_name);
+    let mimic_enum_name_ident = Ident::new(mimic_enum_name.as_str(), Span::call_site());
+    let handler_name = format!("{}Handler", input_enum_name);
+    let handler_ident = Ident::new(handler_name.as_str(), Span::call_site());
+
+    let enum_lifetime = mimic_enum_lifetime(mimics);
+    let lifetime = lifetime_params(&enum_lifetime);
+
+    let mut methods = vec![];
+    let mut dispatch_arms = vec![];
+    let mut method_owners: Vec<(Ident, &str)> = vec![];
+    for mimic in mimics {
+        let concatenated_name = format!("{}{}", input_enum_name, mimic.name);
+        let mimic_name_ident = Ident::new(concatenated_name.as_str(), Span::call_site());
+        let method_ident = handler_method_ident(mimic)?;
+
+        // Variants such as `Get` and `GET` would otherwise define the same method
+        if let Some((_, owner)) = method_owners.iter().find(|(ident, _)| *ident == method_ident) {
+            return Err(syn::Error::new_spanned(
+                &mimic.ident,
+                format!("Variant {} has the same handler method `{}` as variant {}", mimic.name, method_ident, owner),
+            ));
+        }
+        method_owners.push((method_ident.clone(), &mimic.name));
+        let method_doc = format!("Handle `{}::{}`", input_enum_name, mimic.name);
+
+        let param_list = mimic
+            .fields
+            .iter()
+            .map(|f| f.ident.clone())
+            .collect::<Vec<Ident>>();
+        let param_type_list = mimic
+            .fields
+            .iter()
+            .map(|f| f.instance_type())
+            .collect::<Vec<TokenStream2>>();
+        let item_list = (0..mimic.fields.len())
+            .map(|i| Ident::new(format!("f{}", i).as_str(), Span::call_site()))
+            .collect::<Vec<Ident>>();
+
+        methods.push(quote! {
+            #[doc = #method_doc]
+            fn #method_ident(&mut self, #(#param_list: #param_type_list),*) -> Self::Output;
+        });
+
+        if mimic.fields.is_empty() {
+            dispatch_arms.push(quote! {
+                #mimic_enum_name_ident::#mimic_name_ident => handler.#method_ident(),
+            });
+        } else {
+            dispatch_arms.push(quote! {
+                #mimic_enum_name_ident::#mimic_name_ident { inner } => {
+                    handler.#method_ident(#(inner.instance.#item_list),*)
+                }
+            });
+        }
+    }
+
+    let handler_doc = format!(
+        "Handles each variant of `{}` with its fields, called through `{}::dispatch`",
+        input_enum_name, mimic_enum_name
+    );
+
+    Ok(vec![quote! {
+        #[doc = #handler_doc]
+        pub trait #handler_ident #lifetime {
+            /// What every method returns
+            type Output;
+
+            #(#methods)*
+        }
+
+        impl #lifetime #mimic_enum_name_ident #lifetime {
+            /// Call the method of `handler` for this variant with its fields
+            pub fn dispatch<H: #handler_ident #lifetime + ?Sized>(self, handler: &mut H) -> H::Output {
+                match self {
+                    #(#dispatch_arms)*
+                }
+            }
+        }
+    }])
+}
+
+#[proc_macro_derive(Mimic, attributes(mimic))]
 pub fn generate_mimic_for(input: TokenStream) -> TokenStream {
     let ast = syn::parse_macro_input!(input);
 
@@ -0,0 +1,56 @@
+// Parsing command-line arguments into a mimic enumeration, where the first argument names the variant and the rest
+// are its fields given as GNU-style flags or positionally:
+//
+//      cats unicolor --name Tom --color "orange tabby"
+
+use crate::MimicEnum;
+
+use std::ffi::OsString;
+
+const HELP_REQUESTED: &str = "Usage requested with --help";
+
+/// Parse `argv`, including the program name, into a mimic enumeration.
+///
+/// Usage is printed to stdout on `--help` (or `-h` in place of the variant name), and to stderr along with the error
+/// when the arguments do not parse. Either way an error is returned, leaving the caller to decide how to exit.
+pub fn mimic_from_args<T, I>(args: I) -> Result<T, &'static str>
+where
+    T: MimicEnum,
+    I: IntoIterator<Item = OsString>,
+{
+    let mut args = args.into_iter();
+    let program = args
+        .next()
+        .and_then(|program| program.into_string().ok())
+        .unwrap_or_else(|| "<program>".to_owned());
+    let usage = format!(
+        "Usage: {} <command> [arguments]\n\nCommands:\n{}",
+        program,
+        T::usage()
+    );
+
+    let parsed = args
+        .map(|arg| {
+            arg.into_string()
+                .map_err(|_| "Command-line argument is not valid unicode")
+        })
+        .collect::<Result<Vec<String>, &'static str>>()
+        .and_then(|args| {
+            let help_requested = args.first().is_some_and(|arg| arg == "-h")
+                || args
+                    .iter()
+                    .take_while(|arg| *arg != "--")
+                    .any(|arg| arg == "--help");
+            if help_requested {
+                return Err(HELP_REQUESTED);
+            }
+            T::try_from_flags(args)
+        });
+
+    match parsed {
+        Err(HELP_REQUESTED) => println!("{}", usage),
+        Err(e) => eprintln!("error: {}\n\n{}", e, usage),
+        Ok(_) => {}
+    }
+    parsed
+}
@@ -0,0 +1,159 @@
+// GNU-style flag arguments, such as `--name uno --client-version=1.5.2 -v`.
+//
+// Long flags are matched against field names as in `mimic_name_matches` once their `-` are read as `_`, so that
+// `--client-version` names `client_version`. Short flags are matched against the character given with
+// `#[mimic(short = 'n')]`. A `bool` field is set only by the presence of its flag. Anything that is not a flag fills the
+// remaining fields in order, and `--` ends flag parsing so that what follows is always positional. A nested field,
+// whether flagged or positional, takes its variant name followed by that variant's fields, as in `--shape square 3`.
+
+use crate::{
+    line::join_mimic_args_literals, mimic_name_matches, nested::count_nested_args, MimicFieldData,
+    MimicMetadata,
+};
+
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+fn is_bool_field(field: &MimicFieldData) -> bool {
+    field.type_ == "bool"
+}
+
+/// A negative number such as `-5` or `-.5` is positional, not a short flag
+fn is_short_flag(arg: &str) -> bool {
+    let mut chars = arg.chars();
+    chars.next() == Some('-')
+        && chars
+            .next()
+            .is_some_and(|c| !c.is_ascii_digit() && c != '.' && c != '-')
+}
+
+/// Take the arguments of a nested value from `first` onwards, grouped into one argument as `group_nested_args` does
+fn take_nested_value(
+    nested: &[MimicMetadata],
+    first: String,
+    args: &mut vec::IntoIter<String>,
+) -> Result<String, &'static str> {
+    let mut value_args = vec![first];
+    value_args.extend(args.by_ref());
+    let count = count_nested_args(nested, &value_args)?;
+    *args = value_args.split_off(count).into_iter();
+    Ok(join_mimic_args_literals(&value_args, &[]))
+}
+
+fn set_flag(
+    arranged: &mut [Option<String>],
+    index: usize,
+    value: String,
+) -> Result<(), &'static str> {
+    if arranged[index].is_some() {
+        return Err("Duplicated flag in arguments");
+    }
+    arranged[index] = Some(value);
+    Ok(())
+}
+
+/// Rearrange a mix of flags and positional arguments into the positional order of `meta`'s fields.
+///
+/// `bool` fields are only set by their flag and never take a positional argument, so they are `false` when it is
+/// absent. Every other field must be given as a flag or positionally.
+pub fn arrange_flag_args(
+    meta: &MimicMetadata,
+    args: Vec<String>,
+) -> Result<Vec<String>, &'static str> {
+    let mut arranged: Vec<Option<String>> = vec![None; meta.fields.len()];
+    let mut positional = vec![];
+    let mut options_ended = false;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if options_ended {
+            positional.push(arg);
+        } else if arg == "--" {
+            options_ended = true;
+        } else if let Some(long) = arg.strip_prefix("--") {
+            let (name, inline_value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_owned())),
+                None => (long, None),
+            };
+            let index = meta
+                .fields
+                .iter()
+                .position(|field| mimic_name_matches(field.name, &name.replace('-', "_")))
+                .ok_or("Unknown long flag in arguments")?;
+
+            let value = match inline_value {
+                Some(value) => value,
+                None if is_bool_field(&meta.fields[index]) => "true".to_owned(),
+                None => args.next().ok_or("Missing value for long flag")?,
+            };
+            let value = match meta.fields[index].nested {
+                Some(nested) => take_nested_value(&nested(), value, &mut args)?,
+                None => value,
+            };
+            set_flag(&mut arranged, index, value)?;
+        } else if is_short_flag(&arg) {
+            // Short flags may be clustered as in `-pq`, with the last taking a value as in `-pn uno` or `-pnuno`
+            for (position, short) in arg.char_indices().skip(1) {
+                let index = meta
+                    .fields
+                    .iter()
+                    .position(|field| field.short == Some(short))
+                    .ok_or("Unknown short flag in arguments")?;
+
+                if is_bool_field(&meta.fields[index]) {
+                    set_flag(&mut arranged, index, "true".to_owned())?;
+                    continue;
+                }
+
+                let attached = &arg[position + short.len_utf8()..];
+                let value = if attached.is_empty() {
+                    args.next().ok_or("Missing value for short flag")?
+                } else {
+                    attached.to_owned()
+                };
+                let value = match meta.fields[index].nested {
+                    Some(nested) => take_nested_value(&nested(), value, &mut args)?,
+                    None => value,
+                };
+                set_flag(&mut arranged, index, value)?;
+                break;
+            }
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    // A variadic last field not given as a flag takes every leftover positional argument, and may be empty
+    let has_rest = meta.fields.last().is_some_and(|field| field.rest);
+    let rest_flag = if has_rest {
+        arranged.pop().flatten()
+    } else {
+        None
+    };
+
+    let mut positional = positional.into_iter();
+    for (slot, field) in arranged.iter_mut().zip(meta.fields.iter()) {
+        if slot.is_some() {
+            continue;
+        }
+        if is_bool_field(field) {
+            *slot = Some("false".to_owned());
+            continue;
+        }
+        *slot = match (positional.next(), field.nested) {
+            (Some(arg), Some(nested)) => Some(take_nested_value(&nested(), arg, &mut positional)?),
+            (Some(arg), None) => Some(arg),
+            (None, _) => return Err("Missing value for a field not given as a flag"),
+        };
+    }
+
+    let rest = match rest_flag {
+        Some(value) => vec![value],
+        None if has_rest => positional.by_ref().collect(),
+        None => vec![],
+    };
+    if positional.next().is_some() {
+        return Err("Too many positional arguments given alongside flags");
+    }
+
+    Ok(arranged.into_iter().flatten().chain(rest).collect())
+}
@@ -0,0 +1,146 @@
+// Sources of interactive input, where each line is asked for with a prompt.
+//
+// Front-ends read through `MimicInput` rather than from stdin directly, so that a flow of prompts can be driven by a
+// script in tests. `MimicStdio` and `MimicIo` need `std`, while `MimicScript` only needs `alloc`.
+
+use crate::MimicMetadata;
+
+use alloc::{collections::VecDeque, string::String, vec::Vec};
+
+/// A source of lines of input, each asked for with a prompt.
+pub trait MimicInput {
+    /// Show `prompt` and read one line without its line ending, or `None` once the input has ended
+    fn read_line(&mut self, prompt: &str) -> Result<Option<String>, &'static str>;
+}
+
+/// Ask for every field of `meta` in order with its prompt, collecting one argument per field.
+///
+/// Returns `None` if the input ends before every field has been answered.
+pub fn prompt_mimic_fields<I: MimicInput + ?Sized>(
+    meta: &MimicMetadata,
+    input: &mut I,
+) -> Result<Option<Vec<String>>, &'static str> {
+    let mut args = Vec::with_capacity(meta.fields.len());
+    for field in &meta.fields {
+        match input.read_line(field.prompt)? {
+            Some(line) => args.push(line),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(args))
+}
+
+/// Remove a trailing `\n` or `\r\n`
+#[cfg(feature = "std")]
+fn trim_line_ending(mut line: String) -> String {
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    line
+}
+
+/// Prompts on stdout and reads lines from stdin
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct MimicStdio;
+
+#[cfg(feature = "std")]
+impl MimicStdio {
+    pub fn new() -> Self {
+        MimicStdio
+    }
+}
+
+#[cfg(feature = "std")]
+impl MimicInput for MimicStdio {
+    fn read_line(&mut self, prompt: &str) -> Result<Option<String>, &'static str> {
+        MimicIo::new(std::io::stdin().lock(), std::io::stdout().lock()).read_line(prompt)
+    }
+}
+
+/// Prompts on any writer and reads lines from any buffered reader, such as a file or a socket
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct MimicIo<R, W> {
+    reader: R,
+    writer: W,
+}
+
+#[cfg(feature = "std")]
+impl<R, W> MimicIo<R, W>
+where
+    R: std::io::BufRead,
+    W: std::io::Write,
+{
+    pub fn new(reader: R, writer: W) -> Self {
+        MimicIo { reader, writer }
+    }
+
+    /// The reader and writer, such as to inspect what was written
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, W> MimicInput for MimicIo<R, W>
+where
+    R: std::io::BufRead,
+    W: std::io::Write,
+{
+    fn read_line(&mut self, prompt: &str) -> Result<Option<String>, &'static str> {
+        write!(self.writer, "{} ", prompt)
+            .and_then(|_| self.writer.flush())
+            .map_err(|_| "Failed to write a prompt")?;
+
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(trim_line_ending(line))),
+            Err(_) => Err("Failed to read a line of input"),
+        }
+    }
+}
+
+/// Answers each prompt with the next of a list of responses, recording the prompts it was shown, so that an
+/// interactive flow can be tested. The input ends once the responses run out.
+#[derive(Debug, Default, Clone)]
+pub struct MimicScript {
+    responses: VecDeque<String>,
+    prompts: Vec<String>,
+}
+
+impl MimicScript {
+    pub fn new<S: Into<String>>(responses: impl IntoIterator<Item = S>) -> Self {
+        MimicScript {
+            responses: responses.into_iter().map(Into::into).collect(),
+            prompts: Vec::new(),
+        }
+    }
+
+    /// Every prompt shown so far, in order, including one answered by the end of input
+    pub fn prompts(&self) -> &[String] {
+        &self.prompts
+    }
+
+    /// The responses not yet read
+    pub fn remaining(&self) -> usize {
+        self.responses.len()
+    }
+}
+
+impl MimicInput for MimicScript {
+    fn read_line(&mut self, prompt: &str) -> Result<Option<String>, &'static str> {
+        self.prompts.push(prompt.into());
+        Ok(self.responses.pop_front())
+    }
+}
+
+impl<I: MimicInput + ?Sized> MimicInput for &mut I {
+    fn read_line(&mut self, prompt: &str) -> Result<Option<String>, &'static str> {
+        (**self).read_line(prompt)
+    }
+}
@@ -1,79 +1,374 @@
+//! It looks at an enum and walks like a struct.
+//!
+//! `#[derive(Mimic)]` on an enum generates a mimic structure for each variant, which parses the variant's fields from
+//! string arguments and formats them back, along with a mimic enumeration which parses the variant name followed by
+//! its fields:
+//!
+//! ```
+//! use mimicry::*;
+//!
+//! #[allow(unused)]
+//! #[derive(Debug, Mimic)]
+//! enum Request {
+//!     Connect { name: String, client_version: String },
+//!     Disconnect,
+//! }
+//!
+//! let mimic = "connect uno 1.5.2".parse::<MimicRequest>().unwrap();
+//! assert_eq!(mimic.to_string(), "Connect uno 1.5.2");
+//! assert_eq!(mimic.to_mimic_args(), vec!["Connect", "uno", "1.5.2"]);
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 extern crate mimicry_derive;
 
 pub use mimicry_arg::*;
 pub use mimicry_derive::Mimic;
 
-use std::{fmt::Debug, str::FromStr};
+// Re-exported so that code generated by `#[derive(Mimic)]` can name serde without the caller depending on it
+#[cfg(feature = "serde")]
+pub use serde;
+
+#[cfg(feature = "std")]
+mod cli;
+mod flags;
+mod input;
+mod line;
+mod named;
+mod nested;
+#[cfg(feature = "std")]
+pub mod repl;
+mod sample;
+
+#[cfg(feature = "std")]
+pub use cli::mimic_from_args;
+pub use flags::arrange_flag_args;
+pub use input::{prompt_mimic_fields, MimicInput, MimicScript};
+#[cfg(feature = "std")]
+pub use input::{MimicIo, MimicStdio};
+pub use line::{
+    join_mimic_args, parse_mimic_line, split_mimic_line, split_mimic_line_literals, MimicLine,
+    MimicPrefix,
+};
+pub use named::{arrange_mimic_line_args, arrange_named_args, arrange_named_strs};
+pub use nested::group_nested_args;
+pub use sample::{MimicRng, MimicSample};
+
+/// Fixed-size arrays of arguments are only accepted by the `MimicArgN` of matching length, so their arity is checked
+/// by the compiler while a `Vec` keeps its runtime check:
+///
+/// ```
+/// use mimicry::*;
+///
+/// let arg = MimicArg2::<isize, String>::try_from(["1", "2"]).unwrap();
+/// assert_eq!(arg.f0, 1);
+/// assert_eq!(arg.f1, "2");
+///
+/// assert!(MimicArg2::<isize, String>::try_from(vec!["1".to_owned(), "2".to_owned(), "3".to_owned()]).is_err());
+/// ```
+///
+/// ```compile_fail
+/// use mimicry::*;
+///
+/// let arg = MimicArg2::<isize, String>::try_from(["1", "2", "3"]);
+/// ```
+// Compiled only while collecting doctests, so that these examples stay out of the crate documentation
+#[cfg(doctest)]
+pub struct MimicArgArrayArity;
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+    str::FromStr,
+};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MimicFieldData {
     pub name: &'static str,                // the field name
     pub type_: &'static str,               // the filed type (ie: isize)
     pub type_arguments: Vec<&'static str>, // <A, B, C>
+    pub short: Option<char>,               // the single-character flag, as in `-n`
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_nested"))]
+    pub nested: Option<fn() -> Vec<MimicMetadata>>, // the variants of a nested mimic enumeration
+    pub rest: bool,                        // the last field, taking every remaining argument
+    pub prompt: &'static str, // the question asked for the field, ie: `name (String)` unless given
+    pub constraints: Vec<&'static str>, // checked once parsed, ie: `within 0.0..=1.0` or `one of red|black`
 }
 
+/// Nested metadata is held as a function so that it is only built when asked for, such as when serialized
+#[cfg(feature = "serde")]
+fn serialize_nested<S>(
+    nested: &Option<fn() -> Vec<MimicMetadata>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serde::Serialize::serialize(&nested.map(|metadata| metadata()), serializer)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MimicMetadata {
     pub name: &'static str,         // variant name
     pub fields: Vec<MimicFieldData>, // variant fields
 }
 
+impl MimicFieldData {
+    /// The field's type as written, ie: `Vec<String>`
+    pub fn type_name(&self) -> String {
+        if self.type_arguments.is_empty() {
+            self.type_.to_owned()
+        } else {
+            format!("{}<{}>", self.type_, self.type_arguments.join(", "))
+        }
+    }
+
+    /// How the field is shown in usage text, ie: `<name (-n): String>`, `<words: String...>` when it takes the
+    /// remaining arguments, or `<ratio: f32, within 0.0..=1.0>` when it is constrained
+    pub fn usage(&self) -> String {
+        let type_name = match (self.rest, self.type_arguments.first()) {
+            (true, Some(item_type)) if self.type_ == "Vec" => format!("{}...", item_type),
+            (true, _) => format!("{}...", self.type_name()),
+            (false, _) => self.type_name(),
+        };
+        let type_name = if self.constraints.is_empty() {
+            type_name
+        } else {
+            format!("{}, {}", type_name, self.constraints.join(", "))
+        };
+        match self.short {
+            Some(short) => format!("<{} (-{}): {}>", self.name, short, type_name),
+            None => format!("<{}: {}>", self.name, type_name),
+        }
+    }
+}
+
+impl MimicMetadata {
+    /// The variant name followed by each of its fields, ie: `Connect <name: String> <client_version: String>`
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.to_owned();
+        for field in &self.fields {
+            usage.push(' ');
+            usage.push_str(&field.usage());
+        }
+        usage
+    }
+}
+
+/// Implemented by `#[derive(Mimic)]` for the generated mimic enumeration, so that it can be parsed generically.
+pub trait MimicEnum:
+    Sized + TryFrom<Vec<String>, Error = &'static str> + FromStr<Err = &'static str>
+{
+    /// Metadata for every variant, in declaration order
+    fn metadata() -> Vec<MimicMetadata>;
+
+    /// Parse the variant name followed by GNU-style flags for its fields
+    fn try_from_flags(args: Vec<String>) -> Result<Self, &'static str>;
+
+    /// Parse a line of console input as a command marked by `prefix`, or as chat. See `parse_mimic_line`.
+    fn parse_line(line: &str, prefix: MimicPrefix) -> Result<MimicLine<Self>, &'static str> {
+        parse_mimic_line(line, prefix)
+    }
+
+    /// One line of usage per variant
+    fn usage() -> String {
+        Self::metadata()
+            .iter()
+            .map(|meta| format!("  {}", meta.usage()))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// The length checked by `#[mimic(min_len = 1, max_len = 32)]`, which is the number of characters of a string and the
+/// number of items of a list.
+pub trait MimicLen {
+    fn mimic_len(&self) -> usize;
+}
+
+impl MimicLen for str {
+    fn mimic_len(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl MimicLen for String {
+    fn mimic_len(&self) -> usize {
+        self.as_str().mimic_len()
+    }
+}
+
+impl MimicLen for Cow<'_, str> {
+    fn mimic_len(&self) -> usize {
+        self.as_ref().mimic_len()
+    }
+}
+
+impl<T> MimicLen for Vec<T> {
+    fn mimic_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, D> MimicLen for MimicList<T, D>
+where
+    T: FromStr,
+    D: MimicDelimiter,
+{
+    fn mimic_len(&self) -> usize {
+        self.output_list.len()
+    }
+}
+
+impl<T: MimicLen + ?Sized> MimicLen for &T {
+    fn mimic_len(&self) -> usize {
+        (**self).mimic_len()
+    }
+}
+
+/// Compare a declared variant or field name against user input, ignoring ASCII case, so that `DropPattern` is matched
+/// by `droppattern`.
+pub fn mimic_name_matches(declared: &str, given: &str) -> bool {
+    declared.eq_ignore_ascii_case(given)
+}
+
+/// The separator between the items of a `MimicList`, given as its second type parameter.
+///
+/// Lists nest by giving the inner list a different delimiter, as in `MimicList<MimicList<u8, MimicComma>>` for
+/// `1,2;3,4`.
+pub trait MimicDelimiter {
+    const DELIMITER: char;
+}
+
+/// Separates items with `;`, the default for `MimicList`
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub struct MimicSemicolon;
+
+/// Separates items with `,`, as `MimicArgN` does
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub struct MimicComma;
+
+/// Separates items with `|`
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub struct MimicPipe;
+
+/// Separates items with `:`, as in `PATH`-like lists
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub struct MimicColon;
+
+impl MimicDelimiter for MimicSemicolon {
+    const DELIMITER: char = ';';
+}
+
+impl MimicDelimiter for MimicComma {
+    const DELIMITER: char = ',';
+}
+
+impl MimicDelimiter for MimicPipe {
+    const DELIMITER: char = '|';
+}
+
+impl MimicDelimiter for MimicColon {
+    const DELIMITER: char = ':';
+}
+
+/// Whether `args` for `meta` must be rearranged, and so owned, before they can be parsed positionally. That is when the
+/// variant has nested or variadic fields, or when any argument is given as `key=value`.
+pub fn mimic_args_need_arranging<S: AsRef<str>>(meta: &MimicMetadata, args: &[S]) -> bool {
+    meta.fields
+        .iter()
+        .any(|field| field.nested.is_some() || field.rest)
+        || args
+            .iter()
+            .any(|arg| named::split_named_arg(meta, arg.as_ref()).is_some())
+}
+
 #[derive(Debug, PartialEq, Default)]
-pub struct MimicList<T>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct MimicList<T, D = MimicSemicolon>
 where
     T: FromStr,
+    D: MimicDelimiter,
 {
     output_list: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    delimiter: PhantomData<D>,
 }
 
-impl<T> MimicList<T>
+impl<T, D> MimicList<T, D>
 where
     T: FromStr,
+    D: MimicDelimiter,
 {
     pub fn new(t: Vec<T>) -> Self {
-        MimicList::<T> { output_list: t }
+        MimicList::<T, D> {
+            output_list: t,
+            delimiter: PhantomData,
+        }
     }
 }
 
-impl<T> From<MimicList<T>> for Vec<T>
+impl<T, D> From<MimicList<T, D>> for Vec<T>
 where
     T: FromStr,
+    D: MimicDelimiter,
 {
-    fn from(ibl: MimicList<T>) -> Self {
+    fn from(ibl: MimicList<T, D>) -> Self {
         ibl.output_list
     }
 }
 
-impl<T> FromStr for MimicList<T>
+impl<T, D> FromStr for MimicList<T, D>
 where
     T: FromStr,
+    D: MimicDelimiter,
 {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let csv = s
-            .split(";")
-            .filter_map(|s: &str| {
-                let t = s.trim();
-
-                if !t.is_empty() {
-                    Some(t)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<&str>>();
+        let mut csv = split_mimic_segments(s, D::DELIMITER)?;
         if csv.is_empty() {
-            return Err("No semi-colon-separated list found in input for MimicList");
+            return Err("No delimiter-separated list found in input for MimicList");
+        }
+        // A single trailing delimiter ends the list rather than leaving a missing item, so `1;2;` has two items
+        if csv.len() > 1 && csv.last() == Some(&None) {
+            csv.pop();
         }
 
-        let parsed = match csv
+        let parsed = csv
             .iter()
-            .map(|item| item.parse::<T>())
-            .collect::<Result<Vec<T>, <T as FromStr>::Err>>()
-        {
-            Ok(p) => p,
-            Err(_) => return Err("Failed to parse as MimicList<T>"),
-        };
+            .map(|item| match item {
+                Some(item) => item
+                    .parse::<T>()
+                    .map_err(|_| "Failed to parse as MimicList<T>"),
+                None => Err("Missing item between delimiters in MimicList"),
+            })
+            .collect::<Result<Vec<T>, &'static str>>()?;
+
+        Ok(MimicList::<T, D>::new(parsed))
+    }
+}
 
-        Ok(MimicList::<T>::new(parsed))
+impl<T, D> Display for MimicList<T, D>
+where
+    T: FromStr + Display,
+    D: MimicDelimiter,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items = self
+            .output_list
+            .iter()
+            .map(|item| quote_mimic_segment(&item.to_string(), D::DELIMITER))
+            .collect::<Vec<String>>();
+        write!(f, "{}", items.join(D::DELIMITER.encode_utf8(&mut [0; 4])))
     }
 }
@@ -0,0 +1,183 @@
+// Splitting a line of user input into mimic arguments, and joining mimic arguments back into a line.
+//
+// The two functions are inverses of each other:
+//
+//      split_mimic_line(&join_mimic_args(&args)) == Ok(args)
+//
+// Arguments are separated by whitespace. Double quotes group an argument containing whitespace and may escape `"` and
+// `\` with a backslash. Single quotes group an argument verbatim. Outside of quotes a backslash escapes the next
+// character.
+//
+// An argument containing `=` may be read as a named `key=value` argument. Quoting or escaping its first `=` keeps it
+// positional, which is why `join_mimic_args` quotes every argument containing `=`.
+//
+// A console mixing commands and chat marks commands with a prefix, as in `/kick uno`, and `parse_mimic_line` tells the
+// two apart. Doubling the prefix escapes it, so `//hello` is the chat `/hello`.
+
+use crate::MimicEnum;
+
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+/// What marks a line of console input as a command rather than chat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MimicPrefix {
+    /// Every line is a command
+    None,
+    /// Lines starting with this character are commands, and anything else is chat
+    Char(char),
+}
+
+impl Default for MimicPrefix {
+    fn default() -> Self {
+        MimicPrefix::Char('/')
+    }
+}
+
+/// A line of console input, either a parsed command or chat
+#[derive(Debug, Clone, PartialEq)]
+pub enum MimicLine<T> {
+    Command(T),
+    Text(String),
+}
+
+/// Parse a line as a command if it starts with `prefix`, or keep it as chat otherwise.
+///
+/// A doubled prefix is chat starting with a single prefix, as in `//hello` for `/hello`. The command after the prefix
+/// is parsed with `FromStr`, and an error is returned if it does not parse.
+pub fn parse_mimic_line<T: MimicEnum>(
+    line: &str,
+    prefix: MimicPrefix,
+) -> Result<MimicLine<T>, &'static str> {
+    let command = match prefix {
+        MimicPrefix::None => line,
+        MimicPrefix::Char(c) => match line.strip_prefix(c) {
+            Some(rest) if rest.starts_with(c) => return Ok(MimicLine::Text(rest.to_owned())),
+            Some(rest) => rest,
+            None => return Ok(MimicLine::Text(line.to_owned())),
+        },
+    };
+    command.parse::<T>().map(MimicLine::Command)
+}
+
+/// Split a line of input into whitespace-separated arguments, honoring quoting and escapes.
+pub fn split_mimic_line(line: &str) -> Result<Vec<String>, &'static str> {
+    split_mimic_line_literals(line).map(|(args, _)| args)
+}
+
+// Record whether the first `=` of an argument was quoted or escaped
+fn push_arg_char(arg: &mut String, first_eq: &mut Option<bool>, c: char, literal: bool) {
+    if c == '=' && first_eq.is_none() {
+        *first_eq = Some(literal);
+    }
+    arg.push(c);
+}
+
+/// Like `split_mimic_line`, also marking each argument whose first `=` was quoted or escaped, so that it is read
+/// positionally rather than as `key=value`
+#[doc(hidden)]
+pub fn split_mimic_line_literals(line: &str) -> Result<(Vec<String>, Vec<bool>), &'static str> {
+    let mut args = vec![];
+    let mut literal = vec![];
+    let mut current = String::new();
+    let mut first_eq = None;
+    // Tracks whether an argument was started, so that `""` yields an empty argument rather than nothing
+    let mut in_arg = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_arg {
+                    args.push(core::mem::take(&mut current));
+                    literal.push(first_eq.take().unwrap_or(false));
+                    in_arg = false;
+                }
+            }
+            '"' => {
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped @ ('"' | '\\')) => {
+                                push_arg_char(&mut current, &mut first_eq, escaped, true)
+                            }
+                            Some(other) => {
+                                current.push('\\');
+                                push_arg_char(&mut current, &mut first_eq, other, true);
+                            }
+                            None => return Err("Unterminated double quote in mimic line"),
+                        },
+                        Some(other) => push_arg_char(&mut current, &mut first_eq, other, true),
+                        None => return Err("Unterminated double quote in mimic line"),
+                    }
+                }
+            }
+            '\'' => {
+                in_arg = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(other) => push_arg_char(&mut current, &mut first_eq, other, true),
+                        None => return Err("Unterminated single quote in mimic line"),
+                    }
+                }
+            }
+            '\\' => {
+                in_arg = true;
+                match chars.next() {
+                    Some(escaped) => push_arg_char(&mut current, &mut first_eq, escaped, true),
+                    None => return Err("Trailing backslash in mimic line"),
+                }
+            }
+            other => {
+                in_arg = true;
+                push_arg_char(&mut current, &mut first_eq, other, false);
+            }
+        }
+    }
+
+    if in_arg {
+        args.push(current);
+        literal.push(first_eq.unwrap_or(false));
+    }
+
+    Ok((args, literal))
+}
+
+/// Join arguments into a single line which `split_mimic_line` will split back into the same arguments.
+pub fn join_mimic_args(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| quote_mimic_arg(arg, true))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Like `join_mimic_args`, except that an argument containing `=` is only quoted when it is marked as literal, so that
+/// it keeps being read as `key=value` otherwise
+pub(crate) fn join_mimic_args_literals(args: &[String], literal: &[bool]) -> String {
+    args.iter()
+        .enumerate()
+        .map(|(i, arg)| quote_mimic_arg(arg, literal.get(i).copied().unwrap_or(false)))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn quote_mimic_arg(arg: &str, quote_eq: bool) -> String {
+    let needs_quotes = arg.is_empty()
+        || arg.chars().any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\') || (quote_eq && c == '='));
+    if !needs_quotes {
+        return arg.to_owned();
+    }
+
+    let mut quoted = String::with_capacity(arg.len() + 2);
+    quoted.push('"');
+    for c in arg.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
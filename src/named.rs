@@ -0,0 +1,134 @@
+// Named `key=value` arguments, such as `name=uno client_version=1.5.2`.
+//
+// Named arguments may be given in any order, and may follow positional arguments which fill the leading fields. They
+// are rearranged into positional order so that they can be parsed exactly like positional input. Only an argument
+// whose key names a field is read as named, so that a value such as `a=b` is positional otherwise.
+
+use crate::{mimic_name_matches, nested::group_nested_literals, MimicMetadata};
+
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+/// Split `key=value` into the index of the field of `meta` named by `key` and the value, if there is such a field
+pub(crate) fn split_named_arg<'a>(meta: &MimicMetadata, arg: &'a str) -> Option<(usize, &'a str)> {
+    let (key, value) = arg.split_once('=')?;
+    let index = meta
+        .fields
+        .iter()
+        .position(|field| mimic_name_matches(field.name, key))?;
+    Some((index, value))
+}
+
+/// Group nested fields and then arrange named arguments, as parsing owned arguments does, where `literal` marks the
+/// arguments to read positionally even if they look like `key=value`, as `split_mimic_line_literals` does for a
+/// quoted `=`
+#[doc(hidden)]
+pub fn arrange_mimic_line_args(
+    meta: &MimicMetadata,
+    args: Vec<String>,
+    literal: &[bool],
+) -> Result<Vec<String>, &'static str> {
+    let (args, literal) = group_nested_literals(meta, args, literal)?;
+    arrange_named_literals(meta, args, &literal)
+}
+
+/// Rearrange a mix of positional and `key=value` arguments into the positional order of `meta`'s fields.
+///
+/// Keys are matched against field names as in `mimic_name_matches`, and an argument whose key names no field is
+/// positional. Input without any named arguments is returned untouched, leaving the argument count to be checked by
+/// the parser.
+///
+/// Once positional arguments reach a variadic last field, it takes every remaining argument verbatim, so that a
+/// `key=value` within the rest of a line is not mistaken for a field. A variadic field that is not given is empty.
+pub fn arrange_named_args(meta: &MimicMetadata, args: Vec<String>) -> Result<Vec<String>, &'static str> {
+    arrange_named_literals(meta, args, &[])
+}
+
+fn arrange_named_literals(
+    meta: &MimicMetadata,
+    args: Vec<String>,
+    literal: &[bool],
+) -> Result<Vec<String>, &'static str> {
+    let is_named = |(i, arg): (usize, &String)| {
+        !literal.get(i).copied().unwrap_or(false) && split_named_arg(meta, arg).is_some()
+    };
+    if !args.iter().enumerate().any(is_named) {
+        return Ok(args);
+    }
+
+    Ok(arrange_strs_literals(meta, &args, literal)?
+        .into_iter()
+        .map(str::to_owned)
+        .collect())
+}
+
+/// Like `arrange_named_args`, except that the arranged arguments borrow from `args`, as does the value of each
+/// `key=value` argument.
+pub fn arrange_named_strs<'a, S: AsRef<str>>(
+    meta: &MimicMetadata,
+    args: &'a [S],
+) -> Result<Vec<&'a str>, &'static str> {
+    arrange_strs_literals(meta, args, &[])
+}
+
+fn arrange_strs_literals<'a, S: AsRef<str>>(
+    meta: &MimicMetadata,
+    args: &'a [S],
+    literal: &[bool],
+) -> Result<Vec<&'a str>, &'static str> {
+    let rest_index = meta
+        .fields
+        .last()
+        .filter(|field| field.rest)
+        .map(|_| meta.fields.len() - 1);
+    let mut arranged: Vec<Option<&'a str>> = vec![None; meta.fields.len()];
+    let mut rest = vec![];
+    let mut positional_count = 0;
+    let mut seen_named = false;
+
+    for (i, arg) in args.iter().map(|arg| arg.as_ref()).enumerate() {
+        if !seen_named && Some(positional_count) == rest_index {
+            rest.push(arg);
+            continue;
+        }
+
+        let named = match literal.get(i) {
+            Some(true) => None,
+            _ => split_named_arg(meta, arg),
+        };
+        match named {
+            Some((index, value)) => {
+                seen_named = true;
+                if arranged[index].is_some() {
+                    return Err("Duplicated field in key=value arguments");
+                }
+                arranged[index] = Some(value);
+            }
+            None => {
+                if seen_named {
+                    return Err("Positional argument given after a key=value argument");
+                }
+                if positional_count >= arranged.len() {
+                    return Err("Too many positional arguments given before key=value arguments");
+                }
+                arranged[positional_count] = Some(arg);
+                positional_count += 1;
+            }
+        }
+    }
+
+    if let Some(index) = rest_index {
+        match arranged[index].take() {
+            Some(value) if rest.is_empty() => rest.push(value),
+            Some(_) => return Err("Duplicated field in key=value arguments"),
+            None => {}
+        }
+        arranged.truncate(index);
+    }
+
+    let mut arranged = arranged
+        .into_iter()
+        .collect::<Option<Vec<&'a str>>>()
+        .ok_or("Missing field in key=value arguments")?;
+    arranged.extend(rest);
+    Ok(arranged)
+}
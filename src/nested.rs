@@ -0,0 +1,93 @@
+// Nested mimic enumerations, where a field's type also derives `Mimic`.
+//
+// A nested field spans a variable number of arguments: the nested variant's name followed by that variant's fields,
+// which may themselves be nested. Those arguments are grouped into one so that every field is again a single argument,
+// which the nested mimic enumeration parses with `FromStr`.
+
+use crate::{
+    line::join_mimic_args_literals, mimic_name_matches, named::split_named_arg, MimicMetadata,
+};
+
+use alloc::{string::String, vec, vec::Vec};
+
+/// Count the arguments spanned by a nested value at the start of `args`
+pub(crate) fn count_nested_args(variants: &[MimicMetadata], args: &[String]) -> Result<usize, &'static str> {
+    let variant_name = args
+        .first()
+        .ok_or("Missing variant name for a nested field")?;
+    let variant = variants
+        .iter()
+        .find(|variant| mimic_name_matches(variant.name, variant_name))
+        .ok_or("Unrecognized variant name for a nested field")?;
+
+    let mut count = 1;
+    for field in &variant.fields {
+        // A variadic last field takes every remaining argument
+        if field.rest {
+            return Ok(args.len());
+        }
+        count += match field.nested {
+            Some(nested) => count_nested_args(&nested(), &args[count.min(args.len())..])?,
+            None => 1,
+        };
+        if count > args.len() {
+            return Err("Insufficient arguments for a nested field");
+        }
+    }
+    Ok(count)
+}
+
+/// Group the arguments of each positional nested field of `meta` into a single argument.
+///
+/// Grouping stops at the first `key=value` argument, as a named nested field is already given as one argument. Input
+/// for a variant without nested fields is returned untouched.
+pub fn group_nested_args(
+    meta: &MimicMetadata,
+    args: Vec<String>,
+) -> Result<Vec<String>, &'static str> {
+    group_nested_literals(meta, args, &[]).map(|(grouped, _)| grouped)
+}
+
+/// Like `group_nested_args`, where `literal` marks the arguments to read positionally even if they look like
+/// `key=value`. Every grouped argument is marked in the result, since it fills a field by position.
+pub(crate) fn group_nested_literals(
+    meta: &MimicMetadata,
+    args: Vec<String>,
+    literal: &[bool],
+) -> Result<(Vec<String>, Vec<bool>), &'static str> {
+    let is_literal = |i: usize| literal.get(i).copied().unwrap_or(false);
+    if meta.fields.iter().all(|field| field.nested.is_none()) {
+        return Ok((args, literal.to_vec()));
+    }
+
+    let mut grouped = vec![];
+    let mut cursor = 0;
+    for field in &meta.fields {
+        if cursor >= args.len()
+            || (!is_literal(cursor) && split_named_arg(meta, &args[cursor]).is_some())
+        {
+            break;
+        }
+
+        match field.nested {
+            Some(nested) => {
+                let count = count_nested_args(&nested(), &args[cursor..])?;
+                let nested_literal = literal.get(cursor..).unwrap_or(&[]);
+                grouped.push(join_mimic_args_literals(
+                    &args[cursor..cursor + count],
+                    nested_literal,
+                ));
+                cursor += count;
+            }
+            None => {
+                grouped.push(args[cursor].clone());
+                cursor += 1;
+            }
+        }
+    }
+    let mut grouped_literal = vec![true; grouped.len()];
+    grouped_literal.extend((cursor..args.len()).map(is_literal));
+    grouped.extend_from_slice(&args[cursor..]);
+
+    Ok((grouped, grouped_literal))
+}
@@ -0,0 +1,117 @@
+//! A read-parse-dispatch loop for any mimic enumeration.
+//!
+//! Each line read is parsed with `FromStr`, with its variant name leading, then handed to a closure. `help` or `?`
+//! prints usage generated from the enum, and `quit` or the end of input ends the loop. Since these are handled before
+//! parsing, they take the place of any variant of the same name.
+//!
+//! ```
+//! use mimicry::repl::MimicRepl;
+//! use mimicry::*;
+//! use std::io::Write;
+//!
+//! #[derive(Debug, Mimic)]
+//! enum Shell {
+//!     Echo { text: String },
+//! }
+//!
+//! let input = std::io::Cursor::new("echo hi\nquit\n");
+//! let mut output = Vec::new();
+//! let mut repl = MimicRepl::new();
+//! repl.run(input, &mut output, |command: MimicShell, out| {
+//!     writeln!(out, "{}", command).map_err(|_| "Failed to write")
+//! })
+//! .unwrap();
+//!
+//! assert_eq!(String::from_utf8(output).unwrap(), "> Echo hi\n> ");
+//! assert_eq!(repl.history(), ["echo hi", "quit"]);
+//! ```
+
+use crate::{mimic_name_matches, MimicEnum};
+
+use std::io::{BufRead, Write};
+
+/// Reads commands until `quit` or the end of input, keeping every line entered as history
+#[derive(Debug, Clone)]
+pub struct MimicRepl {
+    prompt: String,
+    history: Vec<String>,
+}
+
+impl Default for MimicRepl {
+    fn default() -> Self {
+        MimicRepl::new()
+    }
+}
+
+impl MimicRepl {
+    pub fn new() -> Self {
+        MimicRepl {
+            prompt: "> ".to_owned(),
+            history: vec![],
+        }
+    }
+
+    /// Show `prompt` before each line instead of `> `
+    pub fn with_prompt(mut self, prompt: &str) -> Self {
+        self.prompt = prompt.to_owned();
+        self
+    }
+
+    /// Every non-empty line entered so far, in order, including `help` and `quit`
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Read lines from `reader` until `quit` or the end of input, calling `handler` with each command that parses.
+    ///
+    /// Prompts, usage, and errors are written to `writer`, which is also lent to `handler` for its own output. An error
+    /// from parsing or from `handler` is written as `error: ...` and the loop carries on, while failing to read or
+    /// write ends it with an error.
+    pub fn run<T, R, W, F>(
+        &mut self,
+        mut reader: R,
+        mut writer: W,
+        mut handler: F,
+    ) -> Result<(), &'static str>
+    where
+        T: MimicEnum,
+        R: BufRead,
+        W: Write,
+        F: FnMut(T, &mut W) -> Result<(), &'static str>,
+    {
+        loop {
+            write!(writer, "{}", self.prompt)
+                .and_then(|_| writer.flush())
+                .map_err(|_| "Failed to write the REPL prompt")?;
+
+            let mut line = String::new();
+            let read = reader
+                .read_line(&mut line)
+                .map_err(|_| "Failed to read a line for the REPL")?;
+            if read == 0 {
+                return Ok(());
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            self.history.push(line.to_owned());
+
+            if line == "?" || mimic_name_matches("help", line) {
+                writeln!(writer, "Commands:\n{}\n  help\n  quit", T::usage())
+                    .map_err(|_| "Failed to write the REPL usage")?;
+                continue;
+            }
+            if mimic_name_matches("quit", line) {
+                return Ok(());
+            }
+
+            let result = line
+                .parse::<T>()
+                .and_then(|command| handler(command, &mut writer));
+            if let Err(e) = result {
+                writeln!(writer, "error: {}", e).map_err(|_| "Failed to write a REPL error")?;
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+// Random generation of valid values, for property testing parsers built on mimicry.
+//
+// Values are drawn from `MimicRng`, a small seedable generator (SplitMix64), so that a failing case can be reproduced
+// from its seed alone. It is not suitable for anything security related.
+
+use crate::{MimicDelimiter, MimicList};
+
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::str::FromStr;
+
+/// A seedable pseudo-random number generator driving `MimicSample`.
+#[derive(Debug, Clone)]
+pub struct MimicRng {
+    state: u64,
+}
+
+impl MimicRng {
+    pub fn new(seed: u64) -> Self {
+        MimicRng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..bound`. Panics if `bound` is zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "MimicRng::below requires a non-zero bound");
+        self.next_u64() % bound
+    }
+
+    /// A value in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+}
+
+/// Generate a random, valid value of a type.
+///
+/// Every value produced must survive a round trip through `Display` and `FromStr`. This is implemented for the
+/// primitive types, and by `#[derive(Mimic)]` for the mimic structures and enumeration of an enum marked with
+/// `#[mimic(sample)]`.
+pub trait MimicSample: Sized {
+    fn mimic_sample(rng: &mut MimicRng) -> Self;
+}
+
+macro_rules! impl_mimic_sample_for_integers {
+    ($($t:ty),*) => {
+        $(
+            impl MimicSample for $t {
+                fn mimic_sample(rng: &mut MimicRng) -> Self {
+                    rng.next_u64() as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_mimic_sample_for_integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl MimicSample for u128 {
+    fn mimic_sample(rng: &mut MimicRng) -> Self {
+        (u128::from(rng.next_u64()) << 64) | u128::from(rng.next_u64())
+    }
+}
+
+impl MimicSample for i128 {
+    fn mimic_sample(rng: &mut MimicRng) -> Self {
+        u128::mimic_sample(rng) as i128
+    }
+}
+
+// ARBITRARY: floats are kept to a modest range so that they stay readable when printed
+impl MimicSample for f64 {
+    fn mimic_sample(rng: &mut MimicRng) -> Self {
+        (rng.next_f64() - 0.5) * 2.0e6
+    }
+}
+
+impl MimicSample for f32 {
+    fn mimic_sample(rng: &mut MimicRng) -> Self {
+        f64::mimic_sample(rng) as f32
+    }
+}
+
+impl MimicSample for bool {
+    fn mimic_sample(rng: &mut MimicRng) -> Self {
+        rng.next_bool()
+    }
+}
+
+// Includes whitespace, quotes, a backslash, and `=` to exercise argument quoting, but none of the list delimiters
+const SAMPLE_CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 -_.='\"\\";
+
+// ARBITRARY: long enough to be interesting, short enough to be read in a failure message
+const SAMPLE_MAX_STRING_LEN: u64 = 12;
+const SAMPLE_MAX_LIST_LEN: u64 = 4;
+
+impl MimicSample for char {
+    fn mimic_sample(rng: &mut MimicRng) -> Self {
+        SAMPLE_CHARSET[rng.below(SAMPLE_CHARSET.len() as u64) as usize] as char
+    }
+}
+
+impl MimicSample for String {
+    fn mimic_sample(rng: &mut MimicRng) -> Self {
+        let len = 1 + rng.below(SAMPLE_MAX_STRING_LEN);
+        let s = (0..len).map(|_| char::mimic_sample(rng)).collect::<String>();
+
+        // Kept trimmed and non-empty so that a sample reads plainly in a failure message
+        match s.trim() {
+            "" => "_".to_owned(),
+            trimmed => trimmed.to_owned(),
+        }
+    }
+}
+
+impl<T, D> MimicSample for MimicList<T, D>
+where
+    T: FromStr + MimicSample,
+    D: MimicDelimiter,
+{
+    fn mimic_sample(rng: &mut MimicRng) -> Self {
+        // An empty list does not parse, so there is always at least one item
+        let len = 1 + rng.below(SAMPLE_MAX_LIST_LEN);
+        MimicList::new((0..len).map(|_| T::mimic_sample(rng)).collect())
+    }
+}
+
+/// Sampled as the variadic last field of a variant, which may be empty
+impl<T> MimicSample for Vec<T>
+where
+    T: MimicSample,
+{
+    fn mimic_sample(rng: &mut MimicRng) -> Self {
+        let len = rng.below(SAMPLE_MAX_LIST_LEN + 1);
+        (0..len).map(|_| T::mimic_sample(rng)).collect()
+    }
+}
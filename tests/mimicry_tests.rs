@@ -6,6 +6,7 @@ use std::{fmt::Debug, str::FromStr, vec};
 
 #[allow(unused)]
 #[derive(Debug, Mimic)]
+#[mimic(sample, handler)]
 enum RequestAction {
     None,
     Connect {
@@ -199,3 +200,1198 @@ fn make_mimic_list_from_mimicarg2() {
         assert_eq!(a.f1, b.f1);
     });
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn make_requestaction_droppattern_serde_roundtrip() {
+    let responses = vec!["625".to_owned(), "-725".to_owned(), "Denth".to_owned()];
+    let mimic = MimicRequestAction::RequestActionDropPattern {
+        inner: RequestActionDropPattern::try_from(responses).expect("Failed to parse"),
+    };
+
+    let json = serde_json::to_string(&mimic).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let inner = &value["RequestActionDropPattern"]["inner"];
+    assert_eq!(inner["meta"]["name"], "DropPattern");
+    assert_eq!(inner["meta"]["fields"][2]["name"], "pattern");
+    assert_eq!(inner["instance"]["f1"], -725);
+
+    if let MimicRequestAction::RequestActionDropPattern { inner } =
+        serde_json::from_str::<MimicRequestAction>(&json).unwrap()
+    {
+        assert_eq!(inner.meta.name, "DropPattern");
+        assert_eq!(inner.instance.f0, 625);
+        assert_eq!(inner.instance.f1, -725);
+        assert_eq!(inner.instance.f2, "Denth".to_owned());
+    } else {
+        panic!("Deserialized into the wrong variant");
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn make_mimic_list_serde_roundtrip() {
+    let list = "1; 2; 3".parse::<MimicList<isize>>().unwrap();
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(json, "[1,2,3]");
+    assert_eq!(serde_json::from_str::<MimicList<isize>>(&json).unwrap(), list);
+}
+
+#[test]
+fn make_requestaction_droppattern_to_mimic_args() {
+    let responses = vec!["625".to_owned(), "-725".to_owned(), "Denth Star".to_owned()];
+    let mimic = RequestActionDropPattern::try_from(responses.clone()).expect("Failed to parse");
+    assert_eq!(mimic.to_mimic_args(), responses);
+
+    let line = mimic.to_string();
+    assert_eq!(line, "625 -725 \"Denth Star\"");
+    let reparsed = line.parse::<RequestActionDropPattern>().expect("Failed to parse line");
+    assert_eq!(reparsed.to_mimic_args(), responses);
+}
+
+#[test]
+fn make_mimic_requestaction_from_line() {
+    let mimic = "connect uno \"1.5.2 beta\"".parse::<MimicRequestAction>().unwrap();
+    assert_eq!(mimic.to_string(), "Connect uno \"1.5.2 beta\"");
+
+    if let MimicRequestAction::RequestActionConnect { inner } = mimic {
+        assert_eq!(inner.instance.f0, "uno".to_owned());
+        assert_eq!(inner.instance.f1, "1.5.2 beta".to_owned());
+    } else {
+        panic!("Parsed into the wrong variant");
+    }
+
+    let mimic = MimicRequestAction::try_from(vec!["Disconnect".to_owned()]).unwrap();
+    assert_eq!(mimic.to_mimic_args(), vec!["Disconnect".to_owned()]);
+
+    assert!("Disconnect now".parse::<MimicRequestAction>().is_err());
+    assert!("Reconnect".parse::<MimicRequestAction>().is_err());
+    assert!("KEEPALIVE 3".parse::<MimicRequestAction>().is_ok());
+    assert!("keep-alive 3".parse::<MimicRequestAction>().is_err());
+    assert!("".parse::<MimicRequestAction>().is_err());
+}
+
+#[test]
+fn make_mimic_line_roundtrip() {
+    let args = vec![
+        "plain".to_owned(),
+        "".to_owned(),
+        "with space".to_owned(),
+        "\"quoted\"".to_owned(),
+        "back\\slash".to_owned(),
+        "it's".to_owned(),
+    ];
+    let line = join_mimic_args(&args);
+    assert_eq!(split_mimic_line(&line).unwrap(), args);
+
+    assert_eq!(
+        split_mimic_line("  a 'b  c'   d\\ e \"\" ").unwrap(),
+        vec!["a".to_owned(), "b  c".to_owned(), "d e".to_owned(), "".to_owned()]
+    );
+    assert!(split_mimic_line("\"unterminated").is_err());
+}
+
+#[test]
+fn make_mimicarg2_and_mimic_list_to_string() {
+    let list = "1,2;    2, 3      ; 3,4;"
+        .parse::<MimicList<MimicArg2<isize, String>>>()
+        .unwrap();
+    assert_eq!(list.to_string(), "1,2;2,3;3,4");
+    let reparsed = list
+        .to_string()
+        .parse::<MimicList<MimicArg2<isize, String>>>()
+        .unwrap();
+    assert_eq!(reparsed.to_string(), list.to_string());
+}
+
+#[test]
+fn make_requestaction_samples_roundtrip() {
+    let mut rng = MimicRng::new(0x5eed);
+    for _ in 0..64 {
+        let samples = MimicRequestAction::sample_variants(&mut rng);
+        let metadata = MimicRequestAction::metadata();
+        assert_eq!(samples.len(), metadata.len());
+
+        for ((sample, args), meta) in samples.iter().zip(metadata.iter()) {
+            assert_eq!(args[0], meta.name);
+            assert_eq!(args.len(), meta.fields.len() + 1);
+            assert_eq!(&sample.to_mimic_args(), args);
+
+            let parsed = MimicRequestAction::try_from(args.clone()).expect("Sample failed to parse");
+            assert_eq!(&parsed.to_mimic_args(), args);
+
+            let reparsed = sample
+                .to_string()
+                .parse::<MimicRequestAction>()
+                .expect("Sample line failed to parse");
+            assert_eq!(&reparsed.to_mimic_args(), args);
+        }
+    }
+}
+
+#[test]
+fn make_samples_from_same_seed() {
+    let a = MimicRequestAction::mimic_sample(&mut MimicRng::new(7)).to_mimic_args();
+    let b = MimicRequestAction::mimic_sample(&mut MimicRng::new(7)).to_mimic_args();
+    assert_eq!(a, b);
+
+    let mut rng = MimicRng::new(7);
+    for _ in 0..64 {
+        let list = MimicList::<f32>::mimic_sample(&mut rng);
+        assert_eq!(list.to_string().parse::<MimicList<f32>>().unwrap(), list);
+    }
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+#[mimic(sample)]
+enum Shape {
+    Circle {
+        radius: f32,
+    },
+    #[mimic(default)]
+    Square {
+        side: u32,
+    },
+    Point,
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+enum Spawn {
+    Glider {
+        x: i32,
+        y: i32,
+    },
+    Blinker,
+    Block,
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+enum Lamp {
+    Off,
+    #[mimic(default)]
+    On {
+        dimmed: bool,
+        level: u8,
+    },
+}
+
+#[test]
+fn make_default_mimic_variants() {
+    assert_eq!(
+        MimicShape::default().to_mimic_args(),
+        vec!["Square".to_owned(), "0".to_owned()]
+    );
+    assert_eq!(MimicSpawn::default().to_mimic_args(), vec!["Blinker".to_owned()]);
+    assert_eq!(MimicRequestAction::default().to_mimic_args(), vec!["None".to_owned()]);
+
+    // A marked variant's fields take their own defaults, so a `bool` does not have to parse from "0"
+    assert_eq!(MimicLamp::default().to_mimic_args(), to_args("On false 0"));
+}
+
+#[test]
+fn make_requestaction_droppattern_from_named_args() {
+    let expected = vec!["625".to_owned(), "-725".to_owned(), "Denth".to_owned()];
+
+    let mimic = "pattern=Denth y=-725 x=625".parse::<RequestActionDropPattern>().unwrap();
+    assert_eq!(mimic.to_mimic_args(), expected);
+
+    let mimic = "625 pattern=Denth y=-725".parse::<RequestActionDropPattern>().unwrap();
+    assert_eq!(mimic.to_mimic_args(), expected);
+
+    let mimic = "connect name=uno client_version=1.5.2".parse::<MimicRequestAction>().unwrap();
+    assert_eq!(mimic.to_string(), "Connect uno 1.5.2");
+
+    // A key which names no field, or an `=` which is quoted or escaped, leaves the argument positional
+    let mimic = "625 -725 colour=red".parse::<RequestActionDropPattern>().unwrap();
+    assert_eq!(mimic.instance.f2, "colour=red");
+    let mimic = MimicRequestAction::try_from(to_args("Connect a=b 1.5.2")).unwrap();
+    assert_eq!(mimic.to_string(), "Connect \"a=b\" 1.5.2");
+    for line in ["connect \"name=uno\" 1.5.2", "connect 'name=uno' 1.5.2", "connect name\\=uno 1.5.2"] {
+        let mimic = line.parse::<MimicRequestAction>().unwrap();
+        assert_eq!(mimic.to_string(), "Connect \"name=uno\" 1.5.2");
+        assert_eq!(mimic.to_string().parse::<MimicRequestAction>().unwrap().to_string(), mimic.to_string());
+    }
+
+    // Duplicated, missing, and out-of-order arguments
+    assert!("x=625 x=625 y=-725".parse::<RequestActionDropPattern>().is_err());
+    assert!("625 x=625 y=-725 pattern=Denth".parse::<RequestActionDropPattern>().is_err());
+    assert!("x=625 y=-725".parse::<RequestActionDropPattern>().is_err());
+    assert!("x=625 -725 pattern=Denth".parse::<RequestActionDropPattern>().is_err());
+}
+
+#[test]
+fn make_requestaction_from_owned_args_positionally() {
+    // Owned arguments carry no quoting, so a value that looks named still parses back from `to_mimic_args`
+    let mimic = "connect \"client_version=x\" 1.5.2".parse::<MimicRequestAction>().unwrap();
+    let args = mimic.to_mimic_args();
+    assert_eq!(args, to_args("Connect client_version\\=x 1.5.2"));
+    assert_eq!(MimicRequestAction::try_from(args.clone()).unwrap().to_mimic_args(), args);
+
+    let mimic = "repeat 2 stamp square 3 -1 \"times=4\"".parse::<MimicMacro>().unwrap();
+    let args = mimic.to_mimic_args();
+    assert_eq!(MimicMacro::try_from(args.clone()).unwrap().to_mimic_args(), args);
+
+    let mimic = MimicRequestAction::try_from(to_args("Connect client_version=1.5.2 name=uno")).unwrap();
+    assert_eq!(mimic.to_string(), "Connect \"client_version=1.5.2\" \"name=uno\"");
+    assert!(RequestActionDropPattern::try_from(to_args("y=-725 x=625 pattern=Denth")).is_err());
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+enum Console {
+    Join {
+        #[mimic(short = 'n')]
+        name: String,
+        client_version: String,
+    },
+    Spawn {
+        x: i32,
+        y: i32,
+        #[mimic(short = 'p')]
+        paused: bool,
+        #[mimic(short = 'v')]
+        verbose: bool,
+    },
+}
+
+fn to_args(line: &str) -> Vec<String> {
+    split_mimic_line(line).unwrap()
+}
+
+#[test]
+fn make_console_from_flags() {
+    let mimic =
+        MimicConsole::try_from_flags(to_args("join --name uno --client-version 1.5.2")).unwrap();
+    assert_eq!(mimic.to_string(), "Join uno 1.5.2");
+
+    let mimic = MimicConsole::try_from_flags(to_args("join --client-version=1.5.2 -n uno")).unwrap();
+    assert_eq!(mimic.to_string(), "Join uno 1.5.2");
+
+    let mimic = MimicConsole::try_from_flags(to_args("join -nuno 1.5.2")).unwrap();
+    assert_eq!(mimic.to_string(), "Join uno 1.5.2");
+
+    // Negative numbers are positional, absent bool flags are false, and `--` ends flag parsing
+    let mimic = MimicConsole::try_from_flags(to_args("spawn -5 --paused -7")).unwrap();
+    assert_eq!(mimic.to_string(), "Spawn -5 -7 true false");
+
+    let mimic = MimicConsole::try_from_flags(to_args("spawn -pv -- -5 -7")).unwrap();
+    assert_eq!(mimic.to_string(), "Spawn -5 -7 true true");
+
+    let mimic = ConsoleSpawn::try_from_flags(to_args("--y -7 --x=-5")).unwrap();
+    assert_eq!(mimic.to_mimic_args(), to_args("-5 -7 false false"));
+
+    assert!(MimicConsole::try_from_flags(to_args("join --name uno")).is_err());
+    assert!(MimicConsole::try_from_flags(to_args("join --name uno --name dos 1.5.2")).is_err());
+    assert!(MimicConsole::try_from_flags(to_args("join --nickname uno 1.5.2")).is_err());
+    assert!(MimicConsole::try_from_flags(to_args("join -x uno 1.5.2")).is_err());
+    assert!(MimicConsole::try_from_flags(to_args("spawn 1 2 -- --paused")).is_err());
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+enum Greeter {
+    Greet { verbose: bool, name: String },
+}
+
+#[test]
+fn make_greeter_with_leading_bool_from_flags() {
+    // A bool field is only set by its flag, so positional arguments skip over it
+    let mimic = MimicGreeter::try_from_flags(to_args("greet uno")).unwrap();
+    assert_eq!(mimic.to_string(), "Greet false uno");
+
+    let mimic = MimicGreeter::try_from_flags(to_args("greet uno --verbose")).unwrap();
+    assert_eq!(mimic.to_string(), "Greet true uno");
+
+    assert!(MimicGreeter::try_from_flags(to_args("greet true uno")).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn make_console_from_args() {
+    use std::ffi::OsString;
+
+    let argv = |line: &str| {
+        to_args(line)
+            .into_iter()
+            .map(OsString::from)
+            .collect::<Vec<OsString>>()
+    };
+
+    let mimic = Console::from_args(argv("console join -n uno --client-version 1.5.2")).unwrap();
+    assert_eq!(mimic.to_string(), "Join uno 1.5.2");
+
+    let mimic = Console::from_args(argv("console spawn 3 4 --verbose")).unwrap();
+    assert_eq!(mimic.to_string(), "Spawn 3 4 false true");
+
+    assert!(Console::from_args(argv("console --help")).is_err());
+    assert!(Console::from_args(argv("console join --help")).is_err());
+    assert!(Console::from_args(argv("console")).is_err());
+    assert!(Console::from_args(argv("console part uno")).is_err());
+}
+
+#[test]
+fn make_console_usage() {
+    assert_eq!(
+        MimicConsole::usage(),
+        "  Join <name (-n): String> <client_version: String>\n  \
+         Spawn <x: i32> <y: i32> <paused (-p): bool> <verbose (-v): bool>"
+    );
+}
+
+#[test]
+fn make_requestaction_from_index() {
+    assert_eq!(MimicRequestAction::variant_count(), 5);
+    assert_eq!(
+        MimicRequestAction::variant_names(),
+        &["None", "Connect", "Disconnect", "KeepAlive", "DropPattern"]
+    );
+
+    for (index, name) in MimicRequestAction::variant_names().iter().enumerate() {
+        assert_eq!(MimicRequestAction::metadata()[index].name, *name);
+    }
+
+    let mimic = MimicRequestAction::from_index(3, vec!["12763917391823".to_owned()]).unwrap();
+    assert_eq!(mimic.to_string(), "KeepAlive 12763917391823");
+
+    let mimic = MimicRequestAction::from_index(2, vec![]).unwrap();
+    assert_eq!(mimic.to_string(), "Disconnect");
+
+    assert!(MimicRequestAction::from_index(2, vec!["extra".to_owned()]).is_err());
+    assert!(MimicRequestAction::from_index(5, vec![]).is_err());
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+#[mimic(sample)]
+enum Tool {
+    Stamp {
+        #[mimic(nested)]
+        shape: Shape,
+        x: i32,
+    },
+    Erase,
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+#[mimic(sample)]
+enum Macro {
+    Repeat {
+        times: u8,
+        #[mimic(nested)]
+        tool: Tool,
+        label: String,
+    },
+}
+
+#[test]
+fn make_tool_with_nested_shape() {
+    let mimic = "stamp circle 1.5 -3".parse::<MimicTool>().unwrap();
+    assert_eq!(mimic.to_mimic_args(), to_args("Stamp Circle 1.5 -3"));
+
+    if let MimicTool::ToolStamp { inner } = &mimic {
+        assert_eq!(inner.instance.f0.to_string(), "Circle 1.5");
+        assert_eq!(inner.instance.f1, -3);
+    } else {
+        panic!("Parsed into the wrong variant");
+    }
+
+    let mimic = "stamp point 7".parse::<MimicTool>().unwrap();
+    assert_eq!(mimic.to_string(), "Stamp Point 7");
+
+    let mimic = "stamp x=7 shape=\"square side=2\"".parse::<MimicTool>().unwrap();
+    assert_eq!(mimic.to_string(), "Stamp Square 2 7");
+
+    let mimic = "repeat 3 erase \"label=x\"".parse::<MimicMacro>().unwrap();
+    assert_eq!(mimic.to_string(), "Repeat 3 Erase \"label=x\"");
+    let mimic = "repeat 3 erase label=x".parse::<MimicMacro>().unwrap();
+    assert_eq!(mimic.to_string(), "Repeat 3 Erase x");
+
+    let mimic = "repeat 3 stamp circle 1.5 -3 \"twice over\"".parse::<MimicMacro>().unwrap();
+    assert_eq!(mimic.to_string(), "Repeat 3 Stamp Circle 1.5 -3 \"twice over\"");
+
+    assert!("stamp circle -3".parse::<MimicTool>().is_err());
+    assert!("stamp hexagon 2 -3".parse::<MimicTool>().is_err());
+    assert!("stamp".parse::<MimicTool>().is_err());
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+enum Brush {
+    Round { radius: f32 },
+    Flat { width: u32 },
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+enum Canvas {
+    Paint {
+        #[mimic(nested)]
+        brush: Brush,
+        strokes: u8,
+    },
+}
+
+#[test]
+fn make_canvas_with_nested_brush_without_default() {
+    // `MimicBrush` has no `Default`, as no variant of `Brush` is a unit or marked one
+    let mimic = "paint flat 4 2".parse::<MimicCanvas>().unwrap();
+    assert_eq!(mimic.to_mimic_args(), to_args("Paint Flat 4 2"));
+    let mimic = MimicCanvas::try_from_flags(to_args("paint --strokes 3 --brush round 0.5")).unwrap();
+    assert_eq!(mimic.to_string(), "Paint Round 0.5 3");
+}
+
+#[test]
+fn make_tool_with_nested_shape_from_flags() {
+    let mimic = MimicTool::try_from_flags(to_args("stamp --shape square 3 --x 7")).unwrap();
+    assert_eq!(mimic.to_string(), "Stamp Square 3 7");
+    let mimic = MimicTool::try_from_flags(to_args("stamp --x=-3 --shape=circle 1.5")).unwrap();
+    assert_eq!(mimic.to_string(), "Stamp Circle 1.5 -3");
+    let mimic = MimicTool::try_from_flags(to_args("stamp circle 1.5 --x 7")).unwrap();
+    assert_eq!(mimic.to_string(), "Stamp Circle 1.5 7");
+    let mimic = MimicTool::try_from_flags(to_args("stamp --x 7 point")).unwrap();
+    assert_eq!(mimic.to_string(), "Stamp Point 7");
+
+    let mimic = MimicMacro::try_from_flags(to_args("repeat --tool stamp circle 1.5 -3 --times 3 again")).unwrap();
+    assert_eq!(mimic.to_string(), "Repeat 3 Stamp Circle 1.5 -3 again");
+
+    assert!(MimicTool::try_from_flags(to_args("stamp --shape circle")).is_err());
+    assert!(MimicTool::try_from_flags(to_args("stamp --shape square 3 4 --x 7")).is_err());
+}
+
+#[test]
+fn make_nested_metadata_and_samples() {
+    let meta = ToolStamp::metadata();
+    let nested = meta.fields[0].nested.expect("Field should be nested")();
+    assert_eq!(meta.fields[0].type_, "Shape");
+    assert_eq!(nested.len(), 3);
+    assert_eq!(nested[0].name, "Circle");
+    assert!(meta.fields[1].nested.is_none());
+
+    let mut rng = MimicRng::new(34);
+    for _ in 0..64 {
+        for (_, args) in MimicMacro::sample_variants(&mut rng) {
+            let parsed = MimicMacro::try_from(args.clone()).expect("Sample failed to parse");
+            assert_eq!(parsed.to_mimic_args(), args);
+        }
+    }
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+#[mimic(sample)]
+enum Chat {
+    Say {
+        words: Vec<String>,
+    },
+    Kick {
+        name: String,
+        #[mimic(rest)]
+        reason: String,
+    },
+}
+
+#[test]
+fn tell_commands_from_chat() {
+    let line = MimicChat::parse_line("/kick uno \"too loud\"", MimicPrefix::default()).unwrap();
+    match line {
+        MimicLine::Command(mimic) => assert_eq!(mimic.to_string(), "Kick uno \"too loud\""),
+        MimicLine::Text(_) => panic!("Parsed a command as chat"),
+    }
+    let line = MimicChat::parse_line("!say hi", MimicPrefix::Char('!')).unwrap();
+    assert!(matches!(line, MimicLine::Command(MimicChat::ChatSay { .. })));
+    let line = MimicChat::parse_line("say hi", MimicPrefix::None).unwrap();
+    assert!(matches!(line, MimicLine::Command(MimicChat::ChatSay { .. })));
+
+    let line = MimicChat::parse_line("kick uno", MimicPrefix::default()).unwrap();
+    assert!(matches!(line, MimicLine::Text(ref text) if text == "kick uno"));
+    let line = MimicChat::parse_line("//hello", MimicPrefix::default()).unwrap();
+    assert!(matches!(line, MimicLine::Text(ref text) if text == "/hello"));
+    let line = MimicChat::parse_line("/say hi", MimicPrefix::Char('!')).unwrap();
+    assert!(matches!(line, MimicLine::Text(ref text) if text == "/say hi"));
+
+    assert!(MimicChat::parse_line("/dance", MimicPrefix::default()).is_err());
+    assert!(parse_mimic_line::<MimicChat>("/kick", MimicPrefix::default()).is_err());
+}
+
+#[test]
+fn make_chat_with_rest_fields() {
+    let mimic = "say hello there everyone".parse::<MimicChat>().unwrap();
+    assert_eq!(mimic.to_mimic_args(), to_args("Say hello there everyone"));
+
+    let mimic = "say".parse::<MimicChat>().unwrap();
+    assert_eq!(mimic.to_string(), "Say");
+
+    let mimic = "kick uno spamming the lobby".parse::<MimicChat>().unwrap();
+    if let MimicChat::ChatKick { inner } = &mimic {
+        assert_eq!(inner.instance.f0, "uno");
+        assert_eq!(inner.instance.f1, "spamming the lobby");
+    } else {
+        panic!("Parsed into the wrong variant");
+    }
+    assert_eq!(mimic.to_string(), "Kick uno \"spamming the lobby\"");
+    assert_eq!(mimic.to_string().parse::<MimicChat>().unwrap().to_string(), mimic.to_string());
+
+    // The rest of the line is taken verbatim, even where it looks like a named or flag argument
+    let mimic = "kick name=uno".parse::<MimicChat>().unwrap();
+    assert_eq!(mimic.to_string(), "Kick uno");
+    let mimic = "kick uno reason=none given".parse::<MimicChat>().unwrap();
+    assert_eq!(mimic.to_string(), "Kick uno \"reason=none given\"");
+    let mimic = MimicChat::try_from_flags(to_args("kick --name uno too slow")).unwrap();
+    assert_eq!(mimic.to_string(), "Kick uno \"too slow\"");
+    let mimic = MimicChat::try_from_flags(to_args("kick uno -- --loud")).unwrap();
+    assert_eq!(mimic.to_string(), "Kick uno --loud");
+
+    assert!("kick".parse::<MimicChat>().is_err());
+
+    assert_eq!(
+        MimicChat::usage(),
+        "  Say <words: String...>\n  Kick <name: String> <reason: String...>"
+    );
+
+    let mut rng = MimicRng::new(35);
+    for _ in 0..64 {
+        for (_, args) in MimicChat::sample_variants(&mut rng) {
+            let parsed = MimicChat::try_from(args.clone()).expect("Sample failed to parse");
+            assert_eq!(parsed.to_mimic_args(), args);
+        }
+    }
+}
+
+#[mimic_arg_n(3, sep = "|")]
+struct _MimicPipeArg;
+
+#[test]
+fn make_mimic_lists_with_delimiters() {
+    let grid = "1,2,3; 4,5; 6"
+        .parse::<MimicList<MimicList<u8, MimicComma>>>()
+        .unwrap();
+    let rows: Vec<Vec<u8>> = Vec::from(grid).into_iter().map(Vec::from).collect();
+    assert_eq!(rows, vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+
+    let list = "1,a|b : 2,c|d"
+        .parse::<MimicList<MimicArg2<u8, MimicList<String, MimicPipe>>, MimicColon>>()
+        .unwrap();
+    assert_eq!(list.to_string(), "1,a|b:2,c|d");
+
+    let list = "1|uno|1.5; 2|dos|2.5"
+        .parse::<MimicList<MimicPipeArg3<u8, String, f32>>>()
+        .unwrap();
+    assert_eq!(list.to_string(), "1|uno|1.5;2|dos|2.5");
+
+    let arg = MimicPipeArg2::<String, String>::try_from(to_args("a,b c")).unwrap();
+    assert_eq!(arg.to_string(), "a,b|c");
+    assert!("1,uno,1.5".parse::<MimicPipeArg3<u8, String, f32>>().is_err());
+}
+
+#[test]
+fn make_mimic_args_with_empty_values() {
+    let arg = "a,\"\",c".parse::<MimicArg3<String, String, String>>().unwrap();
+    assert_eq!((arg.f0.as_str(), arg.f1.as_str(), arg.f2.as_str()), ("a", "", "c"));
+    assert_eq!(arg.to_string(), "a,\"\",c");
+
+    // A bare empty segment is a missing value where it is, rather than a shorter list
+    assert_eq!(
+        "a,,c".parse::<MimicArg3<String, String, String>>().err(),
+        Some("Missing argument 1 for generic type B in MimicArg3")
+    );
+    assert!("a,c".parse::<MimicArg3<String, String, String>>().is_err());
+    assert_eq!(
+        "a,b,".parse::<MimicArg3<String, String, String>>().err(),
+        Some("Missing argument 2 for generic type C in MimicArg3")
+    );
+    assert!("a,b,".parse::<MimicArg2<String, String>>().is_err());
+
+    let arg = "\" padded, with comma \",\"say \\\"hi\\\"\"".parse::<MimicArg2<String, String>>().unwrap();
+    assert_eq!(arg.f0, " padded, with comma ");
+    assert_eq!(arg.f1, "say \"hi\"");
+    let reparsed = arg.to_string().parse::<MimicArg2<String, String>>().unwrap();
+    assert_eq!((reparsed.f0, reparsed.f1), (arg.f0, arg.f1));
+
+    let list = "a;\"\";c;".parse::<MimicList<String>>().unwrap();
+    assert_eq!(list.to_string(), "a;\"\";c");
+    assert_eq!(Vec::from(list), vec!["a", "", "c"]);
+    assert!("a;;c".parse::<MimicList<String>>().is_err());
+    assert!("a;\"c".parse::<MimicList<String>>().is_err());
+
+    // A quote within a value is not quoting
+    let list = "5\" tall;6' 2\"".parse::<MimicList<String>>().unwrap();
+    let line = list.to_string();
+    assert_eq!(Vec::from(list), vec!["5\" tall", "6' 2\""]);
+    assert_eq!(line.parse::<MimicList<String>>().unwrap().to_string(), line);
+
+    // Quoting nests, so that an empty value survives inside a list
+    let list = "1,\"\"; 2,b".parse::<MimicList<MimicArg2<u8, String>>>().unwrap();
+    let reparsed = list
+        .to_string()
+        .parse::<MimicList<MimicArg2<u8, String>>>()
+        .unwrap();
+    assert_eq!(reparsed.to_string(), list.to_string());
+    let items = Vec::from(reparsed);
+    assert_eq!((items[0].f0, items[0].f1.as_str()), (1, ""));
+}
+
+#[allow(unused, clippy::large_enum_variant)]
+#[derive(Debug, Mimic)]
+#[mimic(sample)]
+enum Config {
+    Apply {
+        setting_0: u8,
+        setting_1: String,
+        setting_2: i32,
+        setting_3: f32,
+        setting_4: bool,
+        setting_5: u64,
+        setting_6: char,
+        setting_7: u8,
+        setting_8: String,
+        setting_9: i32,
+        setting_10: f32,
+        setting_11: bool,
+        setting_12: u64,
+        setting_13: char,
+        setting_14: u8,
+        setting_15: String,
+        setting_16: i32,
+        setting_17: f32,
+        setting_18: bool,
+        setting_19: u64,
+        setting_20: char,
+        setting_21: u8,
+        setting_22: String,
+        setting_23: i32,
+        setting_24: f32,
+        setting_25: bool,
+        setting_26: u64,
+        setting_27: char,
+        setting_28: u8,
+        setting_29: String,
+    },
+    // Between the arities provided by default and those of the `arity-26` feature
+    Tune {
+        knob_0: u8,
+        knob_1: u8,
+        knob_2: u8,
+        knob_3: u8,
+        knob_4: u8,
+        knob_5: u8,
+        knob_6: u8,
+        knob_7: u8,
+        knob_8: u8,
+        knob_9: u8,
+        knob_10: u8,
+        knob_11: u8,
+        knob_12: u8,
+        knob_13: u8,
+        knob_14: u8,
+        knob_15: u8,
+        knob_16: u8,
+        knob_17: u8,
+        knob_18: u8,
+        knob_19: u8,
+    },
+    Reset,
+}
+
+#[test]
+fn make_config_with_thirty_fields() {
+    let line = (0..30)
+        .map(|i| match i % 7 {
+            1 => format!("name{}", i),
+            3 => "1.5".to_owned(),
+            4 => "true".to_owned(),
+            6 => "c".to_owned(),
+            _ => i.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+    let mimic = format!("apply {}", line).parse::<MimicConfig>().unwrap();
+    assert_eq!(mimic.to_string(), format!("Apply {}", line));
+    assert_eq!(ConfigApply::metadata().fields.len(), 30);
+
+    let mimic = "apply setting_29=z".parse::<MimicConfig>();
+    assert!(mimic.is_err());
+
+    let line = (0..20).map(|i| i.to_string()).collect::<Vec<String>>().join(" ");
+    let mimic = format!("tune {}", line).parse::<MimicConfig>().unwrap();
+    assert_eq!(mimic.to_string(), format!("Tune {}", line));
+    assert!(format!("tune {} 20", line).parse::<MimicConfig>().is_err());
+
+    let mut rng = MimicRng::new(38);
+    for (_, args) in MimicConfig::sample_variants(&mut rng) {
+        let parsed = MimicConfig::try_from(args.clone()).expect("Sample failed to parse");
+        assert_eq!(parsed.to_mimic_args(), args);
+    }
+}
+
+#[mimic_arg_n(27..=28)]
+struct _MimicWideArg;
+
+#[test]
+fn make_mimic_arg_beyond_z() {
+    let mut args = vec!["1".to_owned(); 28];
+    let wide = MimicWideArg28::<u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8,
+        u8, u8, u8, u8, bool>::try_from(args.clone());
+    assert_eq!(
+        wide.err(),
+        Some(
+            "Failed to parse as `AB` in MimicWideArg28<A,B,C,D,E,F,G,H,I,J,K,L,M,N,O,P,Q,R,S,T,U,V,W,X,Y,Z,AA,AB>"
+        )
+    );
+
+    args.pop();
+    let wide = MimicWideArg27::<u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8,
+        u8, u8, u8, u8>::try_from(args)
+    .unwrap();
+    assert_eq!(wide.f26, 1);
+}
+
+#[test]
+fn make_mimics_from_borrowed_args() {
+    let line = "connect uno 1.5.2";
+    let words = line.split_whitespace().collect::<Vec<&str>>();
+
+    let mimic = MimicRequestAction::try_from(words.as_slice()).unwrap();
+    assert_eq!(mimic.to_string(), "Connect uno 1.5.2");
+    let mimic = MimicRequestAction::try_from_iter(line.split_whitespace()).unwrap();
+    assert_eq!(mimic.to_string(), "Connect uno 1.5.2");
+
+    let owned = to_args("droppattern y=3 x=2 pattern=\"1,2;3,4\"");
+    let mimic = MimicRequestAction::try_from(owned.as_slice()).unwrap();
+    assert_eq!(mimic.to_string(), "DropPattern 2 3 1,2;3,4");
+
+    let mimic = RequestActionConnect::try_from(&words[1..]).unwrap();
+    assert_eq!(mimic.to_mimic_args(), to_args("uno 1.5.2"));
+    assert!(RequestActionConnect::try_from_iter(["uno"]).is_err());
+    let mimic = MimicChat::try_from_iter("say hello world".split(' ')).unwrap();
+    assert_eq!(mimic.to_string(), "Say hello world");
+
+    let arg = MimicArg2::<u8, String>::try_from_iter("7 seven".split(' ')).unwrap();
+    assert_eq!((arg.f0, arg.f1.as_str()), (7, "seven"));
+    let arg = MimicArg2::<u8, String>::try_from(&["7", "seven"][..]).unwrap();
+    assert_eq!(arg.to_string(), "7,seven");
+    assert!(MimicArg2::<u8, String>::try_from_iter(["7", "seven", "eight"]).is_err());
+    assert!(MimicArg2::<u8, String>::try_from_iter(["seven", "7"]).is_err());
+    assert!(MimicArg0::try_from_iter(Vec::<String>::new()).is_ok());
+}
+
+#[test]
+fn make_tuples_from_mimic_args() {
+    let arg = MimicArg2::<u8, String>::try_from(["1", "uno"]).unwrap();
+    let (number, name): (u8, String) = arg.into();
+    assert_eq!((number, name.as_str()), (1, "uno"));
+
+    let arg = MimicArg3::from((2u8, "dos".to_owned(), true));
+    assert_eq!(arg.to_string(), "2,dos,true");
+    let arg: MimicPipeArg2<u8, bool> = (3, false).into();
+    assert_eq!(arg.to_string(), "3|false");
+
+    let (x, y) = <(i32, i32)>::from_mimic_str("-5, 7").unwrap();
+    assert_eq!((x, y), (-5, 7));
+    let (name, ready) = <(String, bool)>::try_from_mimic_args(to_args("uno true")).unwrap();
+    assert_eq!((name.as_str(), ready), ("uno", true));
+    assert!(<(i32, i32)>::from_mimic_str("-5").is_err());
+    assert!(<(u8,)>::try_from_mimic_args(to_args("256")).is_err());
+    assert!(<()>::from_mimic_str("").is_ok());
+}
+
+#[test]
+fn make_mimicarg_from_arrays() {
+    let arg = MimicArg3::<u8, String, bool>::try_from(["1", "uno", "true"]).unwrap();
+    assert_eq!((arg.f0, arg.f1.as_str(), arg.f2), (1, "uno", true));
+
+    let words = ["2", "dos"];
+    let arg = MimicArg2::<u8, String>::try_from(&words).unwrap();
+    assert_eq!(arg.to_string(), "2,dos");
+    assert!(MimicArg2::<u8, String>::try_from(["dos", "2"]).is_err());
+    assert!(MimicArg0::try_from([]).is_ok());
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+#[mimic(handler)]
+enum Chatter<'a> {
+    Whisper {
+        to: &'a str,
+        text: std::borrow::Cow<'a, str>,
+        volume: u8,
+    },
+    Leave,
+}
+
+#[test]
+fn make_chatter_with_borrowed_fields() {
+    let words = "whisper uno hello 3".split_whitespace().collect::<Vec<&str>>();
+    let mimic = MimicChatter::try_from(words.as_slice()).unwrap();
+    if let MimicChatter::ChatterWhisper { inner } = &mimic {
+        assert_eq!(inner.instance.f0.as_ptr(), words[1].as_ptr());
+        assert!(matches!(inner.instance.f1, std::borrow::Cow::Borrowed("hello")));
+        assert_eq!(inner.instance.f2, 3);
+    } else {
+        panic!("Parsed into the wrong variant");
+    }
+    assert_eq!(mimic.to_string(), "Whisper uno hello 3");
+
+    let words = to_args("whisper volume=1 text=hi to=dos");
+    let mimic = MimicChatter::try_from(words.as_slice()).unwrap();
+    assert_eq!(mimic.to_mimic_args(), to_args("Whisper dos hi 1"));
+    let mimic = MimicChatter::try_from(&["leave"][..]).unwrap();
+    assert_eq!(mimic.to_string(), "Leave");
+    assert!(MimicChatter::try_from(&["whisper", "uno", "hello", "loud"][..]).is_err());
+
+    let metadata = MimicChatter::metadata();
+    let types = metadata[0].fields.iter().map(|field| field.type_).collect::<Vec<&str>>();
+    assert_eq!(types, vec!["&str", "Cow", "u8"]);
+
+    let arg = MimicArg2::<&str, u8>::try_from_borrowed(&["uno", "1"]).unwrap();
+    assert_eq!((arg.f0, arg.f1), ("uno", 1));
+    assert!(MimicArg2::<&str, u8>::try_from_borrowed(&["uno"]).is_err());
+}
+
+mod barn {
+    /// A field type which only shares its name with `std::borrow::Cow`
+    #[derive(Debug, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Cow(pub String);
+
+    impl std::str::FromStr for Cow {
+        type Err = &'static str;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Cow(s.to_owned()))
+        }
+    }
+
+    impl std::fmt::Display for Cow {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+enum Barn {
+    Milk { cow: barn::Cow },
+}
+
+#[test]
+fn make_barn_with_owned_field_named_cow() {
+    let mimic = "milk daisy".parse::<MimicBarn>().unwrap();
+    assert_eq!(mimic.to_string(), "Milk daisy");
+}
+
+// Without the implicit prelude, the derived code must name `String`, `Vec`, and `vec!` itself as it would without `std`
+#[no_implicit_prelude]
+mod without_prelude {
+    use ::core::prelude::rust_2021::*;
+    use ::mimicry::*;
+
+    #[allow(unused)]
+    #[derive(Debug, Mimic)]
+    #[mimic(sample)]
+    pub enum Beacon {
+        Relay {
+            hops: MimicList<u8>,
+        },
+        Ping {
+            seq: u32,
+            #[mimic(rest)]
+            note: ::std::string::String,
+        },
+        Halt,
+    }
+}
+
+#[test]
+fn make_mimics_without_prelude() {
+    use without_prelude::MimicBeacon;
+
+    let mimic = "ping 3 all clear".parse::<MimicBeacon>().unwrap();
+    assert_eq!(mimic.to_string(), "Ping 3 \"all clear\"");
+    assert_eq!("halt".parse::<MimicBeacon>().unwrap().to_mimic_args(), to_args("Halt"));
+    assert_eq!(MimicBeacon::try_from_flags(to_args("relay --hops 1;2")).unwrap().to_string(), "Relay 1;2");
+
+    let mut rng = MimicRng::new(43);
+    for (_, args) in MimicBeacon::sample_variants(&mut rng) {
+        let parsed = MimicBeacon::try_from(args.clone()).expect("Sample failed to parse");
+        assert_eq!(parsed.to_mimic_args(), args);
+    }
+}
+
+fn check_not_reserved(name: &str) -> Result<(), &'static str> {
+    if name.eq_ignore_ascii_case("admin") {
+        return Err("The name is reserved");
+    }
+    Ok(())
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+#[mimic(sample)]
+enum Cat {
+    Tabby {
+        #[mimic(range = 0.0..=1.0)]
+        fluff_ratio: f32,
+        #[mimic(range = 1..10)]
+        lives: u8,
+    },
+    Unicolor {
+        #[mimic(min_len = 1, max_len = 8, validate = check_not_reserved)]
+        name: String,
+        #[mimic(one_of = ["red", "black"], prompt = "What color is the cat?")]
+        color: String,
+    },
+    Litter {
+        #[mimic(max_len = 3)]
+        sizes: MimicList<u8>,
+    },
+}
+
+#[test]
+fn make_cat_with_constraints() {
+    let mimic = "tabby 0.5 9".parse::<MimicCat>().unwrap();
+    assert_eq!(mimic.to_string(), "Tabby 0.5 9");
+    assert!("tabby -7 9".parse::<MimicCat>().is_err());
+    assert!("tabby NaN 9".parse::<MimicCat>().is_err());
+    assert_eq!(
+        "tabby 1.5 9".parse::<MimicCat>().err(),
+        Some("Field `fluff_ratio` of CatTabby is not within 0.0..=1.0")
+    );
+    assert!("tabby 1 10".parse::<MimicCat>().is_err());
+
+    assert!("unicolor tom black".parse::<MimicCat>().is_ok());
+    assert!(MimicCat::try_from_flags(to_args("unicolor --color red --name tom")).is_ok());
+    assert!(MimicCat::try_from(&["unicolor", "tom", "blue"][..]).is_err());
+    assert!(MimicCat::try_from(&["unicolor", "", "red"][..]).is_err());
+    assert!("unicolor thomasina red".parse::<MimicCat>().is_err());
+    assert_eq!("unicolor ADMIN red".parse::<MimicCat>().err(), Some("The name is reserved"));
+
+    let prompts = CatUnicolor::metadata()
+        .fields
+        .iter()
+        .map(|field| field.prompt)
+        .collect::<Vec<&str>>();
+    assert_eq!(prompts, vec!["name (String)", "What color is the cat?"]);
+    assert_eq!(CatLitter::metadata().fields[0].prompt, "sizes (MimicList<u8>)");
+
+    assert!("litter 1;2;3".parse::<MimicCat>().is_ok());
+    assert!("litter 1;2;3;4".parse::<MimicCat>().is_err());
+
+    assert_eq!(
+        MimicCat::usage(),
+        "  Tabby <fluff_ratio: f32, within 0.0..=1.0> <lives: u8, within 1..10>\n  \
+         Unicolor <name: String, length 1..=8> <color: String, one of red|black>\n  \
+         Litter <sizes: MimicList<u8>, length at most 3>"
+    );
+
+    let mut rng = MimicRng::new(45);
+    for _ in 0..64 {
+        for (sample, args) in MimicCat::sample_variants(&mut rng) {
+            let parsed = MimicCat::try_from(args.clone()).expect("Sample failed to parse");
+            assert_eq!(parsed.to_mimic_args(), args);
+            assert_eq!(sample.to_mimic_args(), args);
+        }
+    }
+
+    // Only parsing checks constraints, so the default of `lives` is outside of its range
+    assert!(CatTabby::default().validate().is_err());
+}
+
+const NO_LIVES: u8 = 9;
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+#[mimic(sample)]
+enum Ghost {
+    Haunt {
+        #[mimic(range = NO_LIVES..NO_LIVES)]
+        lives: u8,
+    },
+}
+
+#[test]
+#[should_panic(expected = "Could not sample field `lives` of GhostHaunt within its constraints")]
+fn make_ghost_sample_from_empty_range() {
+    MimicGhost::mimic_sample(&mut MimicRng::new(46));
+}
+
+#[test]
+fn make_cat_from_scripted_prompts() {
+    let index = MimicCat::variant_names().iter().position(|name| *name == "Unicolor").unwrap();
+    let meta = &MimicCat::metadata()[index];
+
+    let mut script = MimicScript::new(["tom", "red", "unused"]);
+    let args = prompt_mimic_fields(meta, &mut script).unwrap().unwrap();
+    assert_eq!(script.prompts(), ["name (String)", "What color is the cat?"]);
+    assert_eq!(script.remaining(), 1);
+    let mimic = MimicCat::from_index(index, args).unwrap();
+    assert_eq!(mimic.to_string(), "Unicolor tom red");
+
+    let mut script = MimicScript::new(["tom"]);
+    assert_eq!(prompt_mimic_fields(meta, &mut script), Ok(None));
+    assert_eq!(script.prompts().len(), 2);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn make_cat_from_prompts_on_io() {
+    let index = MimicCat::variant_names().iter().position(|name| *name == "Unicolor").unwrap();
+    let meta = &MimicCat::metadata()[index];
+
+    let reader = std::io::Cursor::new("tom\r\nblack\n");
+    let mut io = MimicIo::new(reader, Vec::new());
+    let args = prompt_mimic_fields(meta, &mut io).unwrap().unwrap();
+    assert_eq!(args, to_args("tom black"));
+    assert_eq!(io.read_line("More?"), Ok(None));
+    let (_, written) = io.into_inner();
+    assert_eq!(String::from_utf8(written).unwrap(), "name (String) What color is the cat? More? ");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn run_chat_repl() {
+    use mimicry::repl::MimicRepl;
+    use std::io::{Cursor, Write};
+
+    let input = Cursor::new("say hi all\n\nkick\nhelp\nkick uno spam\nshout\nquit\nsay unread\n");
+    let mut output = Vec::new();
+    let mut said = vec![];
+    let mut repl = MimicRepl::new().with_prompt("chat> ");
+    repl.run(input, &mut output, |command: MimicChat, out| match command {
+        MimicChat::ChatSay { inner } => {
+            said.push(inner.instance.f0.join(" "));
+            Ok(())
+        }
+        MimicChat::ChatKick { inner } => {
+            writeln!(out, "kicked {}", inner.instance.f0).map_err(|_| "Failed to write")?;
+            Err("Kicking is disabled")
+        }
+    })
+    .unwrap();
+
+    assert_eq!(said, vec!["hi all"]);
+    assert_eq!(repl.history(), ["say hi all", "kick", "help", "kick uno spam", "shout", "quit"]);
+
+    let output = String::from_utf8(output).unwrap();
+    let expected = format!(
+        "chat> chat> chat> error: {}\nchat> Commands:\n{}\n  help\n  quit\nchat> kicked uno\n\
+         error: Kicking is disabled\nchat> error: {}\nchat> ",
+        "kick".parse::<MimicChat>().err().unwrap(),
+        MimicChat::usage(),
+        "shout".parse::<MimicChat>().err().unwrap(),
+    );
+    assert_eq!(output, expected);
+
+    // The end of input ends the loop as `quit` does
+    let mut repl = MimicRepl::new();
+    assert!(repl.run(Cursor::new("?"), std::io::sink(), |_: MimicChat, _| Ok(())).is_ok());
+    assert_eq!(repl.history(), ["?"]);
+}
+
+/// Counts the requests it is given, and builds the original enum from each
+#[derive(Default)]
+struct RequestCounter {
+    count: usize,
+}
+
+impl RequestActionHandler for RequestCounter {
+    type Output = RequestAction;
+
+    fn none(&mut self) -> RequestAction {
+        self.count += 1;
+        RequestAction::None
+    }
+
+    fn connect(&mut self, name: String, client_version: String) -> RequestAction {
+        self.count += 1;
+        RequestAction::Connect { name, client_version }
+    }
+
+    fn disconnect(&mut self) -> RequestAction {
+        self.count += 1;
+        RequestAction::Disconnect
+    }
+
+    fn keep_alive(&mut self, latest_response_ack: u64) -> RequestAction {
+        self.count += 1;
+        RequestAction::KeepAlive { latest_response_ack }
+    }
+
+    fn drop_pattern(&mut self, x: i32, y: i32, pattern: String) -> RequestAction {
+        self.count += 1;
+        RequestAction::DropPattern { x, y, pattern }
+    }
+}
+
+struct Whispers<'a> {
+    heard: Vec<(&'a str, String)>,
+}
+
+impl<'a> ChatterHandler<'a> for Whispers<'a> {
+    type Output = bool;
+
+    fn whisper(&mut self, to: &'a str, text: std::borrow::Cow<'a, str>, _volume: u8) -> bool {
+        self.heard.push((to, text.into_owned()));
+        true
+    }
+
+    fn leave(&mut self) -> bool {
+        false
+    }
+}
+
+#[test]
+fn dispatch_to_handlers() {
+    let mut counter = RequestCounter::default();
+    let action = "droppattern 2 3 glider".parse::<MimicRequestAction>().unwrap().dispatch(&mut counter);
+    assert!(matches!(action, RequestAction::DropPattern { x: 2, y: 3, ref pattern } if pattern == "glider"));
+    let action = "connect uno 1.5.2".parse::<MimicRequestAction>().unwrap().dispatch(&mut counter);
+    assert!(matches!(action, RequestAction::Connect { ref name, .. } if name == "uno"));
+    let action = "disconnect".parse::<MimicRequestAction>().unwrap().dispatch(&mut counter);
+    assert!(matches!(action, RequestAction::Disconnect));
+    assert_eq!(counter.count, 3);
+
+    let words = ["whisper", "dos", "psst", "1"];
+    let mut whispers = Whispers { heard: vec![] };
+    assert!(MimicChatter::try_from(&words[..]).unwrap().dispatch(&mut whispers));
+    assert!(!MimicChatter::try_from(&["leave"][..]).unwrap().dispatch(&mut whispers));
+    assert_eq!(whispers.heard, vec![("dos", "psst".to_owned())]);
+}
+
+#[allow(unused)]
+#[derive(Debug, Mimic)]
+#[mimic(handler)]
+enum Fetch {
+    HTTPGet { url: String, r#type: String },
+    Get2Fa,
+    Move,
+}
+
+struct Fetcher;
+
+impl FetchHandler for Fetcher {
+    type Output = String;
+
+    fn http_get(&mut self, url: String, r#type: String) -> String {
+        format!("{} as {}", url, r#type)
+    }
+
+    fn get2_fa(&mut self) -> String {
+        "code".to_owned()
+    }
+
+    fn r#move(&mut self) -> String {
+        "moved".to_owned()
+    }
+}
+
+#[test]
+fn dispatch_to_handler_with_acronym_and_raw_field() {
+    let fetched = "httpget /index.html text".parse::<MimicFetch>().unwrap().dispatch(&mut Fetcher);
+    assert_eq!(fetched, "/index.html as text");
+    assert_eq!("get2fa".parse::<MimicFetch>().unwrap().dispatch(&mut Fetcher), "code");
+    assert_eq!("move".parse::<MimicFetch>().unwrap().dispatch(&mut Fetcher), "moved");
+}